@@ -7,16 +7,19 @@ fn main() {
     schema.column_contracts.push(ColumnContract {
         name: "First Column".into(),
         value_contract: ValueContract::new(TypeConstraint::IsType(ValueType::Text), vec![]),
+        coercion_rule: None,
     });
 
     schema.column_contracts.push(ColumnContract {
         name: "Second Column".into(),
         value_contract: ValueContract::new(TypeConstraint::IsType(ValueType::Text), vec![]),
+        coercion_rule: None,
     });
 
     schema.column_contracts.push(ColumnContract {
         name: "Third Column".into(),
         value_contract: ValueContract::new(TypeConstraint::IsType(ValueType::Text), vec![]),
+        coercion_rule: None,
     });
 
     let mut table = Table::from_schema(&schema);
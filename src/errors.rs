@@ -1,3 +1,5 @@
+use crate::value::arithmetic::*;
+use crate::value::coercion::*;
 use crate::value::constraints::*;
 use crate::value::definitions::*;
 use serde::{Deserialize, Serialize};
@@ -18,6 +20,22 @@ pub enum ConstraintError {
 
     #[error("Constraint inapplicable")]
     InvalidConstraintError, // TODO add constraint info
+
+    #[error("Missing key {0}")]
+    MissingKey(String),
+
+    #[error("Unexpected key {0}")]
+    UnexpectedKey(String),
+
+    #[error("Encountered unexpected composite structure")]
+    StructureError { expected: TypeConstraint },
+
+    /// An error of a value inside a composite, at a JSON Pointer like `/a/0`.
+    #[error("{error} at {path}")]
+    AtPath {
+        path: String,
+        error: Box<ConstraintError>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Error)]
@@ -47,11 +65,198 @@ pub enum CoercionError {
 
     #[error("Domain error")] // TODO elaborate on that
     DomainError(String),
+
+    /// The coercion policy does not allow the loss.
+    #[error("Coercion would lose information: {}", .0.kind)]
+    InformationLoss(Box<CoercionLoss>),
+
+    #[error("Unknown coercion rule {0}")]
+    UnknownRule(String),
+}
+
+/// The underlying reason why a literal could not be parsed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Error)]
+#[serde(rename_all = "camelCase")]
+pub enum ParsingCause {
+    #[error("invalid JSON: {0}")]
+    Json(String),
+
+    #[error("invalid ISO 8601: {0}")]
+    Iso8601(String),
+
+    /// A component like the month or the offset is outside of its range.
+    #[error("{0} out of range")]
+    OutOfRange(String),
+}
+
+fn describe_parsing_failure(
+    value: &str,
+    offset: &Option<usize>,
+    expected: &Option<String>,
+    cause: &Option<ParsingCause>,
+    target_type: &Option<ValueType>,
+) -> String {
+    let mut description = format!("Cannot parse {:?}", value);
+    if let Some(target_type) = target_type {
+        description.push_str(&format!(" as {:?}", target_type));
+    }
+    if let Some(offset) = offset {
+        description.push_str(&format!(" at byte {}", offset));
+    }
+    if let Some(expected) = expected {
+        description.push_str(&format!(", expected {}", expected));
+    }
+    if let Some(cause) = cause {
+        description.push_str(&format!(" ({})", cause));
+    }
+    description
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Error)]
 #[serde(rename_all = "camelCase")]
 pub enum ParsingError {
-    #[error("Parsing failed")]
-    CannotParseValue(String),
+    /// `offset` is the byte offset in `value` where parsing failed and
+    /// `expected` describes what would have been valid there.
+    #[error(
+        "{}",
+        describe_parsing_failure(value, offset, expected, cause, target_type)
+    )]
+    CannotParseValue {
+        value: String,
+        offset: Option<usize>,
+        expected: Option<String>,
+        cause: Option<ParsingCause>,
+        target_type: Option<ValueType>,
+    },
+
+    #[error("Ambiguous date {value}, could be read with any of {patterns:?}")]
+    AmbiguousDate {
+        value: String,
+        patterns: Vec<String>,
+    },
+}
+
+impl ParsingError {
+    /// A failure to parse `value`, without details.
+    pub fn cannot_parse(value: &str) -> Self {
+        ParsingError::CannotParseValue {
+            value: value.to_string(),
+            offset: None,
+            expected: None,
+            cause: None,
+            target_type: None,
+        }
+    }
+
+    pub fn at_offset(mut self, at: usize) -> Self {
+        if let ParsingError::CannotParseValue { offset, .. } = &mut self {
+            *offset = Some(at);
+        }
+        self
+    }
+
+    pub fn with_expected(mut self, description: &str) -> Self {
+        if let ParsingError::CannotParseValue { expected, .. } = &mut self {
+            *expected = Some(description.to_string());
+        }
+        self
+    }
+
+    pub fn with_cause(mut self, reason: ParsingCause) -> Self {
+        if let ParsingError::CannotParseValue { cause, .. } = &mut self {
+            *cause = Some(reason);
+        }
+        self
+    }
+
+    pub fn with_target_type(mut self, value_type: &ValueType) -> Self {
+        if let ParsingError::CannotParseValue { target_type, .. } = &mut self {
+            *target_type = Some(value_type.clone());
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Error)]
+#[serde(rename_all = "camelCase")]
+pub enum FormattingError {
+    #[error("Invalid format pattern: {0}")]
+    InvalidPattern(String),
+
+    #[error("Value has no {0} to format")]
+    MissingComponent(String),
+}
+
+/// Errors reading and writing delimiter-separated values, see
+/// `datakit::table::dsv`.
+#[derive(Debug, Clone, Serialize, Deserialize, Error)]
+#[serde(rename_all = "camelCase")]
+pub enum DsvError {
+    /// Writing or reading failed, with the message of the underlying error.
+    #[error("I/O error: {0}")]
+    Io(String),
+
+    /// The header row names the same column more than once.
+    #[error("Duplicate column {0}")]
+    DuplicateColumn(String),
+
+    /// A field cannot be read as the type of its column. `row` counts
+    /// records from 0, as for `ValueError`, and `line` is the line of the
+    /// record in the source, starting at 1.
+    #[error("Cannot read column {column} of row {row} (line {line}): {error}")]
+    ParseError {
+        row: usize,
+        line: u64,
+        column: String,
+        error: Box<ParsingError>,
+    },
+
+    #[error("Cannot write column {column} of row {row}: {error}")]
+    ValueError {
+        row: usize,
+        column: String,
+        error: CoercionError,
+    },
+}
+
+impl From<csv::Error> for DsvError {
+    fn from(error: csv::Error) -> Self {
+        DsvError::Io(error.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Error)]
+#[serde(rename_all = "camelCase")]
+pub enum PathError {
+    #[error("Invalid path {0}")]
+    InvalidPath(String),
+
+    #[error("No value can be placed at {0}")]
+    NotFound(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Error)]
+#[serde(rename_all = "camelCase")]
+pub enum ArithmeticError {
+    #[error("Invalid operand types for {operation}")]
+    InvalidOperands {
+        operation: ArithmeticOperation,
+        left: ValueType,
+        right: ValueType,
+    },
+
+    #[error("Invalid operand type for {operation}")]
+    InvalidOperand {
+        operation: ArithmeticOperation,
+        operand: ValueType,
+    },
+
+    #[error("Integer overflow in {0}")]
+    Overflow(ArithmeticOperation),
+
+    #[error("Division by zero")]
+    DivisionByZero,
+
+    #[error("Undefined operation {0}: {1}")]
+    UndefinedOperation(ArithmeticOperation, String),
 }
@@ -1,6 +1,11 @@
+pub mod dsv;
+
 use crate::errors::*;
+use crate::value::coercion::*;
 use crate::value::constraints::*;
 use crate::value::definitions::*;
+use crate::value::path::*;
+use crate::value::primitives::*;
 use crate::value::traits::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -15,6 +20,47 @@ pub type Column = Vec<Value>;
 pub struct ColumnContract {
     pub name: String,
     pub value_contract: ValueContract,
+    /// Name of the `CoercionRegistry` rule that converts values for the
+    /// column.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coercion_rule: Option<String>,
+}
+
+impl ColumnContract {
+    /// Converts `value` to the type of the column, with the coercion rule
+    /// of the column if it has one.
+    pub fn coerce(
+        &self,
+        value: &Value,
+        registry: &CoercionRegistry,
+    ) -> Result<Value, CoercionError> {
+        self.coerce_reporting(value, registry, &mut Vec::new())
+    }
+
+    /// Like `coerce`, but adds the changes to the value to `losses`.
+    pub fn coerce_reporting(
+        &self,
+        value: &Value,
+        registry: &CoercionRegistry,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        let expected_type = &self.value_contract.expected_type;
+        let value_type = expected_type.value_type();
+        let value = match &self.coercion_rule {
+            Some(name) => registry.convert_with_rule(name, value, &value_type)?,
+            None => registry.convert_reporting(value, &value_type, losses)?,
+        };
+        // Items of arrays and maps of a simple type are converted too.
+        match expected_type {
+            TypeConstraint::ArrayOf(item) | TypeConstraint::MapOf(item) => match item.as_ref() {
+                TypeConstraint::IsType(item_type) if *item_type != ValueType::Composite => registry
+                    .fallback()
+                    .convert_elements_reporting(&value, item_type, losses),
+                _ => Ok(value),
+            },
+            _ => Ok(value),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,18 +76,106 @@ impl Schema {
         }
     }
 
+    /// Maps the column types to a JSON Schema for the rows of a table, as
+    /// objects keyed by column name. Value constraints are not mapped.
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        TypeConstraint::Object(
+            self.column_contracts
+                .iter()
+                .map(|cc| (cc.name.clone(), cc.value_contract.expected_type.clone()))
+                .collect(),
+        )
+        .to_json_schema()
+    }
+
     pub fn from_tuples(tuples: Vec<(&str, ValueContract)>) -> Self {
         let mut new = Self::new();
         for (name, vc) in tuples.iter() {
             new.column_contracts.push(ColumnContract {
                 name: String::from(*name),
                 value_contract: vc.clone(),
+                coercion_rule: None,
             })
         }
         new
     }
 }
 
+/// What `Table::coerce_to` does with cells that cannot be coerced.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FailedCells {
+    /// Replace them with `Value::Missing(Empty::Unexpected)`.
+    Missing,
+    /// Keep the original value.
+    Unchanged,
+}
+
+/// Settings for `Table::coerce_to`.
+pub struct TableCoercion {
+    registry: CoercionRegistry,
+    failed_cells: FailedCells,
+}
+
+impl Default for TableCoercion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableCoercion {
+    pub fn new() -> Self {
+        Self {
+            registry: CoercionRegistry::new(),
+            failed_cells: FailedCells::Missing,
+        }
+    }
+
+    /// Rules and fallback `Coercion` used to convert values.
+    pub fn with_registry(mut self, registry: CoercionRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Defaults to `FailedCells::Missing`.
+    pub fn with_failed_cells(mut self, failed_cells: FailedCells) -> Self {
+        self.failed_cells = failed_cells;
+        self
+    }
+}
+
+/// A cell that `Table::coerce_to` could not coerce.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CellFailure {
+    pub row: usize,
+    pub column: String,
+    pub error: CoercionError,
+}
+
+/// A cell that `Table::coerce_to` changed in a lossy way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CellLoss {
+    pub row: usize,
+    pub column: String,
+    pub loss: CoercionLoss,
+}
+
+/// The cells of a table that could not be coerced, or lost information.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoercionReport {
+    pub failures: Vec<CellFailure>,
+    pub losses: Vec<CellLoss>,
+}
+
+impl CoercionReport {
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty() && self.losses.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Table {
@@ -58,6 +192,31 @@ pub struct Table {
 pub enum ColumnId {
     Ordinal(usize),
     Name(String),
+    /// The values at `path` inside the cells of `column`, e.g. the city in
+    /// a column of addresses. `map_column`, `map_column_if` and
+    /// `validate_column_against_contract` act on the nested values; other
+    /// methods act on the column that contains them.
+    Nested {
+        column: Box<ColumnId>,
+        path: ValuePath,
+    },
+}
+
+impl ColumnId {
+    /// Addresses the values at `path` inside the cells of this column.
+    pub fn nested(self, path: ValuePath) -> Self {
+        ColumnId::Nested {
+            column: Box::new(self),
+            path,
+        }
+    }
+}
+
+/// Stands in for nested values that cells do not have.
+static MISSING: Value = Value::Missing(Empty::Expected);
+
+fn value_at<'a>(value: &'a Value, path: &ValuePath) -> &'a Value {
+    value.get_path(path).unwrap_or(&MISSING)
 }
 
 impl Table {
@@ -92,12 +251,13 @@ impl Table {
     fn column_order(&self, col_name: &str) -> Option<usize> {
         self.column_contracts
             .iter()
-            .position(|c| &c.name == col_name)
+            .position(|c| c.name == col_name)
     }
 
     fn resolve_column_id(&self, col_id: &ColumnId) -> Result<usize, TableError> {
         match col_id {
-            ColumnId::Name(name) => match self.column_order(&name) {
+            ColumnId::Nested { column, .. } => self.resolve_column_id(column),
+            ColumnId::Name(name) => match self.column_order(name) {
                 Some(id) => Ok(id),
                 None => Err(TableError::ColumnError(ColumnError::Unknown(
                     col_id.clone(),
@@ -107,10 +267,29 @@ impl Table {
         }
     }
 
+    /// Resolves the column containing the values, and the path to them
+    /// inside its cells.
+    fn resolve_nested_column_id(
+        &self,
+        col_id: &ColumnId,
+    ) -> Result<(usize, ValuePath), TableError> {
+        match col_id {
+            ColumnId::Nested { column, path } => {
+                let (ordinal, outer) = self.resolve_nested_column_id(column)?;
+                let path = path
+                    .segments()
+                    .iter()
+                    .fold(outer, |joined, segment| joined.join(segment));
+                Ok((ordinal, path))
+            }
+            _ => Ok((self.resolve_column_id(col_id)?, ValuePath::root())),
+        }
+    }
+
     pub fn add_empty_column(&mut self, col_contract: ColumnContract) -> Result<(), TableError> {
         match self.column_order(&col_contract.name) {
             Some(ordinal) => Err(TableError::ColumnError(ColumnError::AlreadyExists {
-                ordinal: ordinal,
+                ordinal,
                 name: col_contract.name.clone(),
             })),
             None => {
@@ -203,12 +382,15 @@ impl Table {
         col_id: &ColumnId,
         column_contract: &ColumnContract,
     ) -> Result<(), TableError> {
-        let ordinal = self.resolve_column_id(col_id)?;
+        let (ordinal, path) = self.resolve_nested_column_id(col_id)?;
         let column = &self.columns[ordinal];
 
         let mut result: Vec<(usize, ValidationError)> = Vec::new();
         for (rowno, value) in column.iter().enumerate() {
-            match column_contract.value_contract.validate(value) {
+            match column_contract
+                .value_contract
+                .validate(value_at(value, &path))
+            {
                 Ok(()) => (),
                 Err(error) => {
                     result.push((rowno, error));
@@ -216,7 +398,7 @@ impl Table {
             }
         }
 
-        if result.len() == 0 {
+        if result.is_empty() {
             Ok(())
         } else {
             Err(TableError::ColumnError(
@@ -277,7 +459,7 @@ impl Table {
 
     pub(crate) fn validate_table_against_contracts(
         &self,
-        col_contracts: &Vec<ColumnContract>,
+        col_contracts: &[ColumnContract],
         strict: bool,
     ) -> Result<(), TableError> {
         let mut result: HashMap<String, Vec<(usize, ValidationError)>> = HashMap::new();
@@ -291,18 +473,15 @@ impl Table {
                 )));
             }
 
-            if let Err(table_error) = self.validate_column_against_contract(
+            if let Err(TableError::ColumnError(ColumnError::ContainsInvalidValues {
+                contract: _,
+                errors,
+            })) = self.validate_column_against_contract(
                 &ColumnId::Ordinal(ordinal),
                 &col_contracts[ordinal],
             ) {
-                if let TableError::ColumnError(ColumnError::ContainsInvalidValues {
-                    contract: _,
-                    errors,
-                }) = table_error
-                {
-                    let key = self.column_contracts[ordinal].name.clone();
-                    result.insert(key, errors);
-                }
+                let key = self.column_contracts[ordinal].name.clone();
+                result.insert(key, errors);
             }
         }
 
@@ -319,20 +498,29 @@ impl Table {
         func: F,
         predicates: &Vec<(ColumnId, P)>,
     ) -> Result<(), TableError> {
-        let ordinal = self.resolve_column_id(&col_id)?;
-        for rowno in 0..self.row_length {
-            let old_value = &self.columns[ordinal][rowno];
+        let (ordinal, path) = self.resolve_nested_column_id(col_id)?;
+        let mut conditions = Vec::with_capacity(predicates.len());
+        for (other_col_id, predicate) in predicates {
+            let (other_col_ordinal, other_path) = self.resolve_nested_column_id(other_col_id)?;
+            conditions.push((other_col_ordinal, other_path, predicate));
+        }
 
-            for (other_col_id, predicate) in predicates {
-                let other_col_ordinal = self.resolve_column_id(other_col_id)?;
-                let other_col_value = &self.columns[other_col_ordinal][rowno];
-                if !predicate(other_col_value) {
-                    continue;
-                }
+        for rowno in 0..self.row_length {
+            let applies = conditions
+                .iter()
+                .all(|(other_col_ordinal, other_path, predicate)| {
+                    predicate(value_at(
+                        &self.columns[*other_col_ordinal][rowno],
+                        other_path,
+                    ))
+                });
+            if !applies {
+                continue;
             }
 
-            let new_value = func(old_value);
-            self.columns[ordinal][rowno] = new_value;
+            if let Some(old_value) = self.columns[ordinal][rowno].get_path_mut(&path) {
+                *old_value = func(old_value);
+            }
         }
         Ok(())
     }
@@ -342,15 +530,89 @@ impl Table {
         col_id: &ColumnId,
         func: F,
     ) -> Result<(), TableError> {
-        let ordinal = self.resolve_column_id(&col_id)?;
+        let (ordinal, path) = self.resolve_nested_column_id(col_id)?;
         for rowno in 0..self.row_length {
-            let old_value = &self.columns[ordinal][rowno];
-            let new_value = func(old_value);
-            self.columns[ordinal][rowno] = new_value;
+            if let Some(old_value) = self.columns[ordinal][rowno].get_path_mut(&path) {
+                *old_value = func(old_value);
+            }
         }
         Ok(())
     }
 
+    /// Infers the type constraints of the columns from their values, e.g.
+    /// `ArrayOf(IsType(Number))` for a column of arrays of numbers. Columns
+    /// whose values have different types, or are all missing, keep their
+    /// contract.
+    pub fn infer_schema(&self) -> Schema {
+        let column_contracts = self
+            .column_contracts
+            .iter()
+            .zip(self.columns.iter())
+            .map(|(cc, column)| match TypeConstraint::infer_all(column) {
+                Some(expected_type) => ColumnContract {
+                    value_contract: ValueContract::new(
+                        expected_type,
+                        cc.value_contract.value_constraints.clone(),
+                    ),
+                    ..cc.clone()
+                },
+                None => cc.clone(),
+            })
+            .collect();
+        Schema { column_contracts }
+    }
+
+    /// Converts the columns of the table to the types of the columns with the
+    /// same name in `schema`, and takes over their contracts. Other columns
+    /// and missing values are kept.
+    ///
+    /// Returns the coerced table together with the cells that could not be
+    /// coerced or lost information. Fails if a column of `schema` is not in
+    /// the table.
+    pub fn coerce_to(
+        &self,
+        schema: &Schema,
+        coercion: &TableCoercion,
+    ) -> Result<(Table, CoercionReport), TableError> {
+        let mut table = self.clone();
+        let mut report = CoercionReport::default();
+
+        for contract in schema.column_contracts.iter() {
+            let ordinal = self.resolve_column_id(&ColumnId::Name(contract.name.clone()))?;
+            for (row, value) in table.columns[ordinal].iter_mut().enumerate() {
+                if let Value::Missing(_) = value {
+                    continue;
+                }
+                let mut losses = Vec::new();
+                match contract.coerce_reporting(value, &coercion.registry, &mut losses) {
+                    Ok(coerced) => {
+                        *value = coerced;
+                        report
+                            .losses
+                            .extend(losses.into_iter().map(|loss| CellLoss {
+                                row,
+                                column: contract.name.clone(),
+                                loss,
+                            }));
+                    }
+                    Err(error) => {
+                        report.failures.push(CellFailure {
+                            row,
+                            column: contract.name.clone(),
+                            error,
+                        });
+                        if coercion.failed_cells == FailedCells::Missing {
+                            *value = Value::Missing(Empty::Unexpected);
+                        }
+                    }
+                }
+            }
+            table.column_contracts[ordinal] = contract.clone();
+        }
+
+        Ok((table, report))
+    }
+
     pub fn check_compatibility(&self, schema: &Schema) -> Result<(), SchemaValidationError> {
         let mut result: Vec<SchemaError> = Vec::new();
 
@@ -369,7 +631,7 @@ impl Table {
             result.push(SchemaError::MissingColumn(their_cc.name.clone()));
         }
 
-        if result.len() > 0 {
+        if !result.is_empty() {
             Err(SchemaValidationError {
                 schema_errors: result,
             })
@@ -0,0 +1,366 @@
+//! Delimiter-Separated Values
+//!
+//! Reads and writes tables as CSV, TSV, etc. Values are converted to text
+//! with `datakit::value::coercion::Coercion`, so date times are written in
+//! ISO 8601 format unless a `DateTimeFormat` is given. Fields are read with
+//! `datakit::value::parsing::Parser`, using `ParserOptions` that can be set
+//! per column.
+
+use crate::errors::*;
+use crate::table::*;
+use crate::value::coercion::*;
+use crate::value::constraints::*;
+use crate::value::definitions::*;
+use crate::value::parsing::*;
+use crate::value::primitives::*;
+use crate::value::traits::*;
+use std::io;
+
+/// Writes tables as delimiter-separated values.
+///
+/// Missing values are written as empty fields, and composite values as
+/// JSON text.
+pub struct DsvWriter {
+    delimiter: u8,
+    has_headers: bool,
+    coercion: Coercion,
+}
+
+impl Default for DsvWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DsvWriter {
+    /// Creates a writer for comma-separated values with a header row.
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+            coercion: Coercion::new(),
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Write the column names as the first row.
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Format date times with `format` instead of using ISO 8601.
+    pub fn with_datetime_format(mut self, format: DateTimeFormat) -> Self {
+        self.coercion = self.coercion.with_datetime_format(format);
+        self
+    }
+
+    fn value_to_field(&self, value: &Value) -> Result<String, CoercionError> {
+        match value {
+            Value::Missing(_) => Ok(String::new()),
+            Value::Text(text) => Ok(text.clone()),
+            _ => match self.coercion.convert(value, &ValueType::Text)? {
+                Value::Text(text) => Ok(text),
+                _ => Err(CoercionError::UnexpectedType),
+            },
+        }
+    }
+
+    pub fn write<W: io::Write>(&self, table: &Table, writer: W) -> Result<(), DsvError> {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
+
+        if self.has_headers {
+            writer.write_record(table.column_contracts().iter().map(|cc| &cc.name))?;
+        }
+
+        for rowno in 0..table.len() {
+            let mut record: Vec<String> = Vec::new();
+            for (column, contract) in table.columns().iter().zip(table.column_contracts()) {
+                let field =
+                    self.value_to_field(&column[rowno])
+                        .map_err(|error| DsvError::ValueError {
+                            row: rowno,
+                            column: contract.name.clone(),
+                            error,
+                        })?;
+                record.push(field);
+            }
+            writer.write_record(&record)?;
+        }
+
+        writer
+            .flush()
+            .map_err(|error| DsvError::Io(error.to_string()))
+    }
+
+    pub fn write_to_string(&self, table: &Table) -> Result<String, DsvError> {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write(table, &mut buffer)?;
+        String::from_utf8(buffer).map_err(|error| DsvError::Io(error.to_string()))
+    }
+}
+
+/// Reads delimiter-separated values into a table.
+///
+/// Fields are read as bare literals, see `ParserOptions::bare`. Empty fields
+/// are always missing values. Columns with a type given by `with_column_type`
+/// are read as that type, and fields that cannot be read are errors. Other
+/// columns get the type of their values, or are read as text if their values
+/// have different types. Fields that cannot be read in these columns, e.g.
+/// numbers outside the range of Unix timestamps, are text.
+///
+/// With `DateTimeDetection::Formats`, dates are read a column at a time with
+/// `DateParser::parse_column`. A column can settle between e.g. `%d/%m/%Y`
+/// and `%m/%d/%Y`, and dates that it leaves ambiguous are errors.
+pub struct DsvReader {
+    delimiter: u8,
+    has_headers: bool,
+    options: ParserOptions,
+    column_options: Vec<(ColumnId, ParserOptions)>,
+    column_types: Vec<(ColumnId, ValueType)>,
+}
+
+impl Default for DsvReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DsvReader {
+    /// Creates a reader for comma-separated values with a header row.
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+            options: ParserOptions::bare(),
+            column_options: Vec::new(),
+            column_types: Vec::new(),
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Read the column names from the first row. Without headers, columns
+    /// are named `column_1`, `column_2`, etc.
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Parser options for columns without options of their own. Literals are
+    /// read as bare literals regardless of the options.
+    pub fn with_parser_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Parser options for one column, by name or position.
+    pub fn with_column_options(mut self, column: ColumnId, options: ParserOptions) -> Self {
+        self.column_options.push((column, options));
+        self
+    }
+
+    /// Reads the values of one column as `value_type`, instead of inferring
+    /// the type. Fields that cannot be read as `value_type` are errors.
+    pub fn with_column_type(mut self, column: ColumnId, value_type: ValueType) -> Self {
+        self.column_types.push((column, value_type));
+        self
+    }
+
+    fn setting_for<'a, T>(
+        settings: &'a [(ColumnId, T)],
+        ordinal: usize,
+        name: &str,
+    ) -> Option<&'a T> {
+        settings
+            .iter()
+            .find(|(column, _)| match column {
+                ColumnId::Ordinal(i) => *i == ordinal,
+                ColumnId::Name(n) => n == name,
+                ColumnId::Nested { .. } => false,
+            })
+            .map(|(_, setting)| setting)
+    }
+
+    fn options_for(&self, ordinal: usize, name: &str) -> &ParserOptions {
+        Self::setting_for(&self.column_options, ordinal, name).unwrap_or(&self.options)
+    }
+
+    fn parser_for(&self, ordinal: usize, name: &str) -> Parser {
+        let options = self.options_for(ordinal, name);
+        Parser::new().with_options(options.clone().with_bare_literals(true))
+    }
+
+    /// Reads the fields as dates with the formats of the column, deciding
+    /// between the formats for the whole column, see `DateParser::parse_column`.
+    fn read_dates(
+        &self,
+        ordinal: usize,
+        name: &str,
+        fields: &[&str],
+    ) -> Option<Vec<Result<Value, ParsingError>>> {
+        let formats = self.options_for(ordinal, name).datetime_formats()?;
+        Some(DateParser::new(formats.to_vec()).parse_column_values(fields))
+    }
+
+    /// Returns the dates if every field is a date or an ambiguous date, and
+    /// at least one field is not missing. Fails at the first ambiguous date.
+    fn date_column(
+        dates: Vec<Result<Value, ParsingError>>,
+    ) -> Option<Result<Column, (usize, ParsingError)>> {
+        let is_date = |result: &Result<Value, ParsingError>| {
+            matches!(
+                result,
+                Ok(Value::DateTime(_)) | Err(ParsingError::AmbiguousDate { .. })
+            )
+        };
+        let dates_or_missing = dates
+            .iter()
+            .all(|result| is_date(result) || matches!(result, Ok(Value::Missing(_))));
+        if !dates_or_missing || !dates.iter().any(is_date) {
+            return None;
+        }
+        Some(
+            dates
+                .into_iter()
+                .enumerate()
+                .map(|(row, result)| result.map_err(|error| (row, error)))
+                .collect(),
+        )
+    }
+
+    fn column_type(values: &[Value]) -> Option<ValueType> {
+        let mut types = values
+            .iter()
+            .filter(|value| !matches!(value, Value::Missing(_)))
+            .map(|value| value.get_value_type());
+        let first = types.next().unwrap_or(&ValueType::Text);
+        if types.all(|value_type| value_type == first) {
+            Some(first.clone())
+        } else {
+            None
+        }
+    }
+
+    fn infer_column<'a>(
+        parser: &Parser,
+        fields: impl Iterator<Item = &'a str> + Clone,
+    ) -> (ValueType, Column) {
+        let mut column: Column = fields
+            .clone()
+            .map(|field| {
+                if field.trim().is_empty() {
+                    Value::Missing(Empty::Expected)
+                } else {
+                    parser
+                        .parse(field)
+                        .unwrap_or_else(|_| Value::Text(field.to_string()))
+                }
+            })
+            .collect();
+
+        let value_type = match Self::column_type(&column) {
+            Some(value_type) => value_type,
+            None => {
+                for (value, field) in column.iter_mut().zip(fields) {
+                    if !matches!(value, Value::Missing(_)) {
+                        *value = Value::Text(field.to_string());
+                    }
+                }
+                ValueType::Text
+            }
+        };
+        (value_type, column)
+    }
+
+    pub fn read<R: io::Read>(&self, reader: R) -> Result<Table, DsvError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .from_reader(reader);
+
+        let records = reader.records().collect::<Result<Vec<_>, _>>()?;
+        let names: Vec<String> = if self.has_headers {
+            reader.headers()?.iter().map(String::from).collect()
+        } else {
+            let width = records.first().map_or(0, |record| record.len());
+            (1..=width).map(|i| format!("column_{}", i)).collect()
+        };
+
+        let mut table = Table::new();
+        let mut columns: Vec<Column> = Vec::new();
+        for (ordinal, name) in names.iter().enumerate() {
+            let parser = self.parser_for(ordinal, name);
+            let fields = records
+                .iter()
+                .map(|record| record.get(ordinal).unwrap_or(""));
+            let parse_error = |row: usize, error: ParsingError| DsvError::ParseError {
+                row,
+                line: records[row].position().map_or(0, |p| p.line()),
+                column: name.clone(),
+                error: Box::new(error),
+            };
+
+            let column_type = Self::setting_for(&self.column_types, ordinal, name);
+            let dates = match column_type {
+                None | Some(ValueType::DateTime) => {
+                    self.read_dates(ordinal, name, &fields.clone().collect::<Vec<_>>())
+                }
+                Some(_) => None,
+            };
+
+            let (value_type, column) = match column_type {
+                Some(value_type) => {
+                    let mut column: Column = Vec::new();
+                    for (row, field) in fields.enumerate() {
+                        let value = match dates.as_ref().map(|dates| &dates[row]) {
+                            _ if field.trim().is_empty() => Value::Missing(Empty::Expected),
+                            Some(Ok(datetime)) => datetime.clone(),
+                            Some(Err(error @ ParsingError::AmbiguousDate { .. })) => {
+                                return Err(parse_error(row, error.clone()))
+                            }
+                            _ => parser
+                                .parse_as(field, value_type)
+                                .map_err(|error| parse_error(row, error))?,
+                        };
+                        column.push(value);
+                    }
+                    (value_type.clone(), column)
+                }
+                None => match dates.and_then(Self::date_column) {
+                    Some(Ok(column)) => (ValueType::DateTime, column),
+                    Some(Err((row, error))) => return Err(parse_error(row, error)),
+                    None => Self::infer_column(&parser, fields),
+                },
+            };
+
+            table
+                .add_empty_column(ColumnContract {
+                    name: name.clone(),
+                    value_contract: ValueContract::new(TypeConstraint::IsType(value_type), vec![]),
+                    coercion_rule: None,
+                })
+                .map_err(|_| DsvError::DuplicateColumn(name.clone()))?;
+            columns.push(column);
+        }
+
+        for rowno in 0..records.len() {
+            let row: Vec<Value> = columns.iter().map(|column| column[rowno].clone()).collect();
+            table.add_row(&row).expect("Rows have one value per column");
+        }
+        Ok(table)
+    }
+
+    pub fn read_from_str(&self, s: &str) -> Result<Table, DsvError> {
+        self.read(s.as_bytes())
+    }
+}
@@ -1,6 +1,9 @@
+pub mod arithmetic;
+pub mod clock;
 pub mod coercion;
 pub mod constraints;
 pub mod definitions;
 pub mod parsing;
+pub mod path;
 pub mod primitives;
 pub mod traits;
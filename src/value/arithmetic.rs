@@ -0,0 +1,431 @@
+//! Value Arithmetic
+//!
+//! Implements the standard arithmetic operators (`+`, `-`, `*`, `/`, `%` and
+//! unary `-`) for `Numeric` and `Value`, as well as a numeric-aware equality
+//! and ordering for `Numeric`.
+//!
+//! # Promotion
+//!
+//! Operands of different numeric sub-types are promoted before the operation
+//! is applied: `Integer` is promoted to `Real`, and `Real` to `Complex`.
+//! Integer operations are checked, i.e. an overflow results in an
+//! `ArithmeticError::Overflow` instead of a wrap-around or a panic.
+//!
+//! Mixed operations between `Integer`, `Unsigned` and `BigInteger` are
+//! carried out with arbitrary precision, and the result is narrowed down to
+//! the smallest integer sub-type that can hold it. This includes operations
+//! between two `Unsigned` values, e.g. `Unsigned(5) - Unsigned(10)` is
+//! `Integer(-5)`.
+//!
+//! `Decimal` sits between `Integer` and `Real`: operations between integers
+//! and decimals are exact, while mixing a decimal with a `Real` gives up
+//! exactness and results in a `Real`. Decimal division is carried out with
+//! a precision of 100 significant digits.
+//!
+//! Dividing two integers results in an `Integer` if the division is exact,
+//! and in a `Real` otherwise.
+//!
+//! # Dates, Times and Durations
+//!
+//! Durations can be added to or subtracted from date times, added to and
+//! subtracted from each other, and multiplied by integers. Subtracting two
+//! date times of the same kind results in the duration between them. See
+//! `DateTime::checked_add` and `DateTime::duration_since` for details.
+//!
+//! # Missing Values
+//!
+//! If any of the operands is `Value::Missing`, the result is missing as well.
+//! An unexpectedly missing operand takes precedence over an expected one.
+//!
+//! # Example
+//!
+//! ```
+//! use datakit::value::definitions::*;
+//! use datakit::value::primitives::*;
+//!
+//! let x = Value::from(2) + Value::from(0.5);
+//! assert_eq!(x.unwrap(), Value::Number(Numeric::Real(2.5)));
+//! ```
+
+use crate::errors::*;
+use crate::value::definitions::*;
+use crate::value::primitives::*;
+use bigdecimal::{ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// An arithmetic operation, used for error reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArithmeticOperation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Neg,
+}
+
+impl std::fmt::Display for ArithmeticOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            ArithmeticOperation::Add => "+",
+            ArithmeticOperation::Sub => "-",
+            ArithmeticOperation::Mul => "*",
+            ArithmeticOperation::Div => "/",
+            ArithmeticOperation::Rem => "%",
+            ArithmeticOperation::Neg => "unary -",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Operands promoted to a common numeric sub-type.
+enum Promoted {
+    Integer(i64, i64),
+    BigInteger(BigInt, BigInt),
+    Decimal(BigDecimal, BigDecimal),
+    Real(f64, f64),
+    Complex((f64, f64), (f64, f64)),
+}
+
+impl Numeric {
+    /// Returns the value of an integer sub-type as a `BigInt`.
+    fn integer_value(&self) -> Option<BigInt> {
+        match self {
+            Numeric::Integer(_) | Numeric::Unsigned(_) | Numeric::BigInteger(_) => self.to_bigint(),
+            _ => None,
+        }
+    }
+
+    fn promote(lhs: &Numeric, rhs: &Numeric) -> Promoted {
+        match (lhs, rhs) {
+            (Numeric::Integer(a), Numeric::Integer(b)) => Promoted::Integer(*a, *b),
+            (Numeric::Complex(_, _), _) | (_, Numeric::Complex(_, _)) => {
+                Promoted::Complex(lhs.as_complex(), rhs.as_complex())
+            }
+            _ => {
+                if let (Some(a), Some(b)) = (lhs.integer_value(), rhs.integer_value()) {
+                    Promoted::BigInteger(a, b)
+                } else if let (Some(a), Some(b)) = (lhs.as_decimal(), rhs.as_decimal()) {
+                    Promoted::Decimal(a, b)
+                } else {
+                    Promoted::Real(
+                        lhs.as_real().unwrap_or(f64::NAN),
+                        rhs.as_real().unwrap_or(f64::NAN),
+                    )
+                }
+            }
+        }
+    }
+
+    fn checked_add(&self, rhs: &Numeric) -> Result<Numeric, ArithmeticError> {
+        match Numeric::promote(self, rhs) {
+            Promoted::Integer(a, b) => a
+                .checked_add(b)
+                .map(Numeric::Integer)
+                .ok_or(ArithmeticError::Overflow(ArithmeticOperation::Add)),
+            Promoted::BigInteger(a, b) => Ok(Numeric::from_bigint(a + b)),
+            Promoted::Decimal(a, b) => Ok(Numeric::Decimal(a + b)),
+            Promoted::Real(a, b) => Ok(Numeric::Real(a + b)),
+            Promoted::Complex((a, b), (c, d)) => Ok(Numeric::Complex(a + c, b + d)),
+        }
+    }
+
+    fn checked_sub(&self, rhs: &Numeric) -> Result<Numeric, ArithmeticError> {
+        match Numeric::promote(self, rhs) {
+            Promoted::Integer(a, b) => a
+                .checked_sub(b)
+                .map(Numeric::Integer)
+                .ok_or(ArithmeticError::Overflow(ArithmeticOperation::Sub)),
+            Promoted::BigInteger(a, b) => Ok(Numeric::from_bigint(a - b)),
+            Promoted::Decimal(a, b) => Ok(Numeric::Decimal(a - b)),
+            Promoted::Real(a, b) => Ok(Numeric::Real(a - b)),
+            Promoted::Complex((a, b), (c, d)) => Ok(Numeric::Complex(a - c, b - d)),
+        }
+    }
+
+    fn checked_mul(&self, rhs: &Numeric) -> Result<Numeric, ArithmeticError> {
+        match Numeric::promote(self, rhs) {
+            Promoted::Integer(a, b) => a
+                .checked_mul(b)
+                .map(Numeric::Integer)
+                .ok_or(ArithmeticError::Overflow(ArithmeticOperation::Mul)),
+            Promoted::BigInteger(a, b) => Ok(Numeric::from_bigint(a * b)),
+            Promoted::Decimal(a, b) => Ok(Numeric::Decimal(a * b)),
+            Promoted::Real(a, b) => Ok(Numeric::Real(a * b)),
+            Promoted::Complex((a, b), (c, d)) => Ok(Numeric::Complex(a * c - b * d, a * d + b * c)),
+        }
+    }
+
+    fn checked_div(&self, rhs: &Numeric) -> Result<Numeric, ArithmeticError> {
+        match Numeric::promote(self, rhs) {
+            Promoted::Integer(_, 0) => Err(ArithmeticError::DivisionByZero),
+            Promoted::Integer(a, b) => match a.checked_rem(b) {
+                Some(0) => a
+                    .checked_div(b)
+                    .map(Numeric::Integer)
+                    .ok_or(ArithmeticError::Overflow(ArithmeticOperation::Div)),
+                Some(_) => Ok(Numeric::Real(a as f64 / b as f64)),
+                None => Err(ArithmeticError::Overflow(ArithmeticOperation::Div)),
+            },
+            Promoted::BigInteger(_, b) if b.is_zero() => Err(ArithmeticError::DivisionByZero),
+            Promoted::BigInteger(a, b) => {
+                if (&a % &b).is_zero() {
+                    Ok(Numeric::from_bigint(a / b))
+                } else {
+                    let quotient = BigDecimal::from(a) / BigDecimal::from(b);
+                    Ok(Numeric::Real(quotient.to_f64().unwrap_or(f64::NAN)))
+                }
+            }
+            Promoted::Decimal(_, b) if b.is_zero() => Err(ArithmeticError::DivisionByZero),
+            Promoted::Decimal(a, b) => Ok(Numeric::Decimal(a / b)),
+            Promoted::Real(a, b) => Ok(Numeric::Real(a / b)),
+            Promoted::Complex((a, b), (c, d)) => {
+                let denominator = c * c + d * d;
+                Ok(Numeric::Complex(
+                    (a * c + b * d) / denominator,
+                    (b * c - a * d) / denominator,
+                ))
+            }
+        }
+    }
+
+    fn checked_rem(&self, rhs: &Numeric) -> Result<Numeric, ArithmeticError> {
+        match Numeric::promote(self, rhs) {
+            Promoted::Integer(_, 0) => Err(ArithmeticError::DivisionByZero),
+            Promoted::Integer(a, b) => a
+                .checked_rem(b)
+                .map(Numeric::Integer)
+                .ok_or(ArithmeticError::Overflow(ArithmeticOperation::Rem)),
+            Promoted::BigInteger(_, b) if b.is_zero() => Err(ArithmeticError::DivisionByZero),
+            Promoted::BigInteger(a, b) => Ok(Numeric::from_bigint(a % b)),
+            Promoted::Decimal(_, b) if b.is_zero() => Err(ArithmeticError::DivisionByZero),
+            Promoted::Decimal(a, b) => Ok(Numeric::Decimal(a % b)),
+            Promoted::Real(a, b) => Ok(Numeric::Real(a % b)),
+            Promoted::Complex(_, _) => Err(ArithmeticError::UndefinedOperation(
+                ArithmeticOperation::Rem,
+                String::from("The remainder is not defined for complex numbers."),
+            )),
+        }
+    }
+
+    fn checked_neg(&self) -> Result<Numeric, ArithmeticError> {
+        match self {
+            Numeric::Integer(i) => i
+                .checked_neg()
+                .map(Numeric::Integer)
+                .ok_or(ArithmeticError::Overflow(ArithmeticOperation::Neg)),
+            Numeric::Real(r) => Ok(Numeric::Real(-r)),
+            Numeric::Complex(re, im) => Ok(Numeric::Complex(-re, -im)),
+            Numeric::Decimal(d) => Ok(Numeric::Decimal(-d)),
+            Numeric::Unsigned(u) => Ok(Numeric::from_bigint(-BigInt::from(*u))),
+            Numeric::BigInteger(b) => Ok(Numeric::from_bigint(-b)),
+        }
+    }
+}
+
+/// Numeric-aware equality, consistent with the ordering below.
+///
+/// Numbers of different sub-types are equal if their values are, e.g.
+/// `Integer(2) == Real(2.0)`.
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+/// Numeric-aware ordering.
+///
+/// Unlike a derived ordering, numbers of different sub-types are compared
+/// by their value, e.g. `Integer(2) < Real(2.5)`. Complex numbers are only
+/// comparable to other numbers if their imaginary part is zero.
+impl PartialOrd for Numeric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match Numeric::promote(self, other) {
+            Promoted::Integer(a, b) => a.partial_cmp(&b),
+            Promoted::BigInteger(a, b) => a.partial_cmp(&b),
+            Promoted::Decimal(a, b) => a.partial_cmp(&b),
+            Promoted::Real(a, b) => a.partial_cmp(&b),
+            Promoted::Complex((a, b), (c, d)) => {
+                if b == 0.0 && d == 0.0 {
+                    a.partial_cmp(&c)
+                } else if a == c && b == d {
+                    Some(Ordering::Equal)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+fn missing_operand(lhs: &Value, rhs: &Value) -> Option<Value> {
+    match (lhs, rhs) {
+        (Value::Missing(Empty::Unexpected), _) | (_, Value::Missing(Empty::Unexpected)) => {
+            Some(Value::Missing(Empty::Unexpected))
+        }
+        (Value::Missing(_), _) | (_, Value::Missing(_)) => Some(Value::Missing(Empty::Expected)),
+        _ => None,
+    }
+}
+
+impl Value {
+    fn apply_binary<F>(
+        &self,
+        rhs: &Value,
+        operation: ArithmeticOperation,
+        op: F,
+    ) -> Result<Value, ArithmeticError>
+    where
+        F: Fn(&Numeric, &Numeric) -> Result<Numeric, ArithmeticError>,
+    {
+        if let Some(missing) = missing_operand(self, rhs) {
+            return Ok(missing);
+        }
+
+        match (self, rhs) {
+            (Value::Number(a), Value::Number(b)) => op(a, b).map(Value::Number),
+            (Value::DateTime(_), _) | (Value::Duration(_), _) | (_, Value::Duration(_)) => {
+                self.apply_temporal(rhs, operation)
+            }
+            _ => Err(self.invalid_operands(rhs, operation)),
+        }
+    }
+
+    fn invalid_operands(&self, rhs: &Value, operation: ArithmeticOperation) -> ArithmeticError {
+        ArithmeticError::InvalidOperands {
+            operation,
+            left: self.get_value_type().clone(),
+            right: rhs.get_value_type().clone(),
+        }
+    }
+
+    fn apply_temporal(
+        &self,
+        rhs: &Value,
+        operation: ArithmeticOperation,
+    ) -> Result<Value, ArithmeticError> {
+        use ArithmeticOperation::*;
+
+        let result = match (operation, self, rhs) {
+            (Add, Value::DateTime(t), Value::Duration(d))
+            | (Add, Value::Duration(d), Value::DateTime(t)) => {
+                t.checked_add(d).map(Value::DateTime)
+            }
+            (Sub, Value::DateTime(t), Value::Duration(d)) => t.checked_sub(d).map(Value::DateTime),
+            (Sub, Value::DateTime(a), Value::DateTime(b)) => {
+                a.duration_since(b).map(Value::Duration)
+            }
+            (Add, Value::Duration(a), Value::Duration(b)) => a.checked_add(b).map(Value::Duration),
+            (Sub, Value::Duration(a), Value::Duration(b)) => a.checked_sub(b).map(Value::Duration),
+            (Mul, Value::Duration(d), Value::Number(n))
+            | (Mul, Value::Number(n), Value::Duration(d)) => match n.to_i64() {
+                Some(factor) => d.checked_mul(factor).map(Value::Duration),
+                None => return Err(self.invalid_operands(rhs, operation)),
+            },
+            _ => return Err(self.invalid_operands(rhs, operation)),
+        };
+
+        result.ok_or_else(|| {
+            ArithmeticError::UndefinedOperation(
+                operation,
+                String::from("The duration does not apply, or the result is out of range."),
+            )
+        })
+    }
+
+    fn apply_neg(&self) -> Result<Value, ArithmeticError> {
+        match self {
+            Value::Missing(empty) => Ok(Value::Missing(empty.clone())),
+            Value::Number(n) => n.checked_neg().map(Value::Number),
+            Value::Duration(d) => d
+                .checked_neg()
+                .map(Value::Duration)
+                .ok_or(ArithmeticError::Overflow(ArithmeticOperation::Neg)),
+            other => Err(ArithmeticError::InvalidOperand {
+                operation: ArithmeticOperation::Neg,
+                operand: other.get_value_type().clone(),
+            }),
+        }
+    }
+}
+
+macro_rules! impl_binary_operator {
+    ( $( $trait:ident, $method:ident => $operation:expr, $checked:ident );+ ) => {
+        $(
+            impl<'a> $trait<&'a Numeric> for &'a Numeric {
+                type Output = Result<Numeric, ArithmeticError>;
+
+                fn $method(self, rhs: &'a Numeric) -> Self::Output {
+                    self.$checked(rhs)
+                }
+            }
+
+            impl $trait for Numeric {
+                type Output = Result<Numeric, ArithmeticError>;
+
+                fn $method(self, rhs: Numeric) -> Self::Output {
+                    self.$checked(&rhs)
+                }
+            }
+
+            impl<'a> $trait<&'a Value> for &'a Value {
+                type Output = Result<Value, ArithmeticError>;
+
+                fn $method(self, rhs: &'a Value) -> Self::Output {
+                    self.apply_binary(rhs, $operation, Numeric::$checked)
+                }
+            }
+
+            impl $trait for Value {
+                type Output = Result<Value, ArithmeticError>;
+
+                fn $method(self, rhs: Value) -> Self::Output {
+                    self.apply_binary(&rhs, $operation, Numeric::$checked)
+                }
+            }
+        )+
+    };
+}
+
+impl_binary_operator! {
+    Add, add => ArithmeticOperation::Add, checked_add;
+    Sub, sub => ArithmeticOperation::Sub, checked_sub;
+    Mul, mul => ArithmeticOperation::Mul, checked_mul;
+    Div, div => ArithmeticOperation::Div, checked_div;
+    Rem, rem => ArithmeticOperation::Rem, checked_rem
+}
+
+impl Neg for &Numeric {
+    type Output = Result<Numeric, ArithmeticError>;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
+
+impl Neg for Numeric {
+    type Output = Result<Numeric, ArithmeticError>;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
+
+impl Neg for &Value {
+    type Output = Result<Value, ArithmeticError>;
+
+    fn neg(self) -> Self::Output {
+        self.apply_neg()
+    }
+}
+
+impl Neg for Value {
+    type Output = Result<Value, ArithmeticError>;
+
+    fn neg(self) -> Self::Output {
+        self.apply_neg()
+    }
+}
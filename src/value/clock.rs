@@ -0,0 +1,69 @@
+//! Clocks
+//!
+//! Constraints relative to the current time, like "not in the future", read
+//! the time from a `Clock`. Use a `FixedClock` for deterministic validation,
+//! e.g. in tests or when re-validating historical data.
+
+use crate::value::primitives::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub trait Clock {
+    /// The current time as a full date time.
+    fn now(&self) -> DateTime;
+}
+
+/// The system clock, in UTC.
+///
+/// A system time beyond the range of years of `Date` is clamped to the first
+/// or last representable instant.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// The last instant of the latest date, or the first instant of the
+    /// earliest date.
+    fn limit(latest: bool) -> DateTime {
+        let (year, month, day) = if latest {
+            (i32::MAX, 12, 31)
+        } else {
+            (i32::MIN, 1, 1)
+        };
+        let (hour, minute, second, fraction) = if latest {
+            (23, 59, 59, 999)
+        } else {
+            (0, 0, 0, 0)
+        };
+        DateTime::Full {
+            date: Date::YearMonthDay { year, month, day },
+            time: Time {
+                hour,
+                minute,
+                second,
+                milli: fraction,
+                micro: fraction,
+                nano: fraction,
+                timezone: TimeZone::Utc,
+            },
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime {
+        let nanos = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_nanos() as i128,
+            Err(error) => -(error.duration().as_nanos() as i128),
+        };
+        DateTime::from_epoch_nanos(nanos).unwrap_or_else(|| SystemClock::limit(nanos > 0))
+    }
+}
+
+/// A clock that always returns the same time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedClock(pub DateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime {
+        self.0.clone()
+    }
+}
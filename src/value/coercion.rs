@@ -7,33 +7,433 @@
 //! 1. Use the `datakit::value::parsing` module to handle to coercion
 //!    from `ValueType::Text` to anything else.
 //! 2. Clean the code up a bit, since it looks like hot trash.
+//!
+//! # Policies
+//!
+//! A `CoercionPolicy` decides which conversions may change a value. Under
+//! the default `Safe` policy only whitespace around text is dropped, while
+//! `Lossy` also rounds numbers and truncates date times. Conversions that
+//! change a value are reported as `CoercionLoss`es by the `*_reporting`
+//! methods, so that data loss can be audited.
+//!
+//! Conversions that are specific to a domain can be added as rules to a
+//! `CoercionRegistry`, which falls back to `Coercion` for everything else.
 
 use crate::errors::*;
 use crate::value::definitions::*;
 use crate::value::parsing::*;
 use crate::value::primitives::*;
 use crate::value::traits::*;
+use bigdecimal::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 //use chrono::{DateTime, Local, Utc};
 
+mod registry;
+
+pub use registry::*;
+
+/// Which changes to a value a coercion may make.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CoercionPolicy {
+    /// Values are kept exactly, including whitespace around text.
+    Strict,
+    /// Values are kept exactly, but whitespace around text is trimmed.
+    #[default]
+    Safe,
+    /// Numbers may be rounded or lose precision, and date times may be
+    /// truncated.
+    Lossy,
+}
+
+impl CoercionPolicy {
+    fn allows(&self, kind: LossKind) -> bool {
+        match self {
+            CoercionPolicy::Strict => false,
+            CoercionPolicy::Safe => kind == LossKind::TrimmedWhitespace,
+            CoercionPolicy::Lossy => true,
+        }
+    }
+}
+
+/// How numbers are rounded to integers under the `Lossy` policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoundingMode {
+    /// Rounds towards zero, e.g. -2.7 to -2.
+    Truncate,
+    Floor,
+    Ceiling,
+    /// Rounds to the nearest integer and ties away from zero, e.g. 2.5 to 3.
+    HalfAwayFromZero,
+    /// Rounds to the nearest integer and ties to the even one, e.g. 2.5 to 2.
+    #[default]
+    HalfEven,
+}
+
+impl RoundingMode {
+    fn round_real(&self, r: f64) -> f64 {
+        match self {
+            RoundingMode::Truncate => r.trunc(),
+            RoundingMode::Floor => r.floor(),
+            RoundingMode::Ceiling => r.ceil(),
+            RoundingMode::HalfAwayFromZero => r.round(),
+            RoundingMode::HalfEven => r.round_ties_even(),
+        }
+    }
+
+    fn round_decimal(&self, d: &BigDecimal) -> BigDecimal {
+        let mode = match self {
+            RoundingMode::Truncate => bigdecimal::RoundingMode::Down,
+            RoundingMode::Floor => bigdecimal::RoundingMode::Floor,
+            RoundingMode::Ceiling => bigdecimal::RoundingMode::Ceiling,
+            RoundingMode::HalfAwayFromZero => bigdecimal::RoundingMode::HalfUp,
+            RoundingMode::HalfEven => bigdecimal::RoundingMode::HalfEven,
+        };
+        d.with_scale_round(0, mode)
+    }
+
+    /// Rounds a number to an integer. Complex numbers are only rounded if
+    /// their imaginary part is zero.
+    fn round(&self, n: &Numeric) -> Option<BigInt> {
+        match n {
+            Numeric::Decimal(d) => Some(self.round_decimal(d).into_bigint_and_exponent().0),
+            Numeric::Real(_) | Numeric::Complex(_, _) => n
+                .as_real()
+                .and_then(|r| BigInt::from_f64(self.round_real(r))),
+            _ => n.to_bigint(),
+        }
+    }
+}
+
+/// The way in which a coercion changed a value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LossKind {
+    /// Whitespace around text was dropped before converting it.
+    TrimmedWhitespace,
+    /// A number was rounded to an integer.
+    Rounded,
+    /// A number was converted to the nearest real number, which does not
+    /// convert back to the original.
+    Precision,
+    /// The time of a full date time was dropped.
+    TruncatedTime,
+    /// The date of a full date time was dropped.
+    TruncatedDate,
+}
+
+impl std::fmt::Display for LossKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            LossKind::TrimmedWhitespace => "whitespace was trimmed",
+            LossKind::Rounded => "the number was rounded",
+            LossKind::Precision => "the number lost precision",
+            LossKind::TruncatedTime => "the time was dropped",
+            LossKind::TruncatedDate => "the date was dropped",
+        };
+        f.write_str(description)
+    }
+}
+
+/// A conversion that changed a value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoercionLoss {
+    pub kind: LossKind,
+    pub original: Value,
+    pub result: Value,
+}
+
 pub struct Coercion {
     parser: Parser,
+    datetime_format: Option<DateTimeFormat>,
+    epoch_unit: EpochUnit,
+    policy: CoercionPolicy,
+    rounding: RoundingMode,
 }
 
 impl Coercion {
     pub fn new() -> Self {
         Self {
             parser: Parser::new(),
+            datetime_format: None,
+            epoch_unit: EpochUnit::Seconds,
+            policy: CoercionPolicy::default(),
+            rounding: RoundingMode::default(),
+        }
+    }
+
+    /// Unit of Unix timestamps when coercing between numbers and date times.
+    /// Defaults to seconds.
+    pub fn with_epoch_unit(mut self, unit: EpochUnit) -> Self {
+        self.epoch_unit = unit;
+        self
+    }
+
+    /// Format date times with `format` when coercing them to text, instead of
+    /// using ISO 8601.
+    pub fn with_datetime_format(mut self, format: DateTimeFormat) -> Self {
+        self.datetime_format = Some(format);
+        self
+    }
+
+    /// Which changes to values are allowed. Defaults to `Safe`.
+    pub fn with_policy(mut self, policy: CoercionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// How numbers are rounded to integers under the `Lossy` policy.
+    /// Defaults to `HalfEven`.
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns `result` if the policy allows the loss, and records the loss.
+    fn lose(
+        &self,
+        kind: LossKind,
+        original: &Value,
+        result: Value,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        let loss = CoercionLoss {
+            kind,
+            original: original.clone(),
+            result,
+        };
+        if !self.policy.allows(kind) {
+            return Err(CoercionError::InformationLoss(Box::new(loss)));
+        }
+        let result = loss.result.clone();
+        losses.push(loss);
+        Ok(result)
+    }
+
+    /// Converts text with `convert` after trimming it, which is reported if
+    /// there was any whitespace.
+    fn convert_text<F>(
+        &self,
+        value: &Value,
+        losses: &mut Vec<CoercionLoss>,
+        convert: F,
+    ) -> Result<Value, CoercionError>
+    where
+        F: FnOnce(&str, &mut Vec<CoercionLoss>) -> Result<Value, CoercionError>,
+    {
+        let s = match value {
+            Value::Text(s) => s,
+            _ => return Err(CoercionError::UnexpectedType),
+        };
+        let trimmed = s.trim();
+        let result = convert(trimmed, losses)?;
+        if trimmed.len() == s.len() {
+            Ok(result)
+        } else {
+            self.lose(LossKind::TrimmedWhitespace, value, result, losses)
         }
     }
 
-    // This needs to be harmonized with the Parser
     fn number_to_text(&self, value: &Value) -> Result<Value, CoercionError> {
         match value {
-            Value::Number(Numeric::Integer(i)) => Ok(Value::Text(i.to_string())),
-            Value::Number(Numeric::Real(r)) => Ok(Value::Text(r.to_string())),
-            Value::Number(Numeric::Complex(_, _)) => Err(CoercionError::DomainError(String::from(
-                "Conversion for complex numbers is currently not supported.",
-            ))),
+            Value::Number(n) => Ok(Value::Text(n.to_string())),
+            _ => Err(CoercionError::UnexpectedType),
+        }
+    }
+
+    fn coercion_failed(&self, value: &Value) -> CoercionError {
+        CoercionError::CoercionFailed {
+            target_type: ValueType::Number,
+            source_value: value.clone(),
+        }
+    }
+
+    fn text_to_numeric(
+        &self,
+        value: &Value,
+        to_ntype: &NumericType,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        self.convert_text(value, losses, |s, losses| match to_ntype {
+            // Decimals are read directly from the text to avoid any detour
+            // through f64.
+            NumericType::Decimal => match BigDecimal::from_str(s) {
+                Ok(d) => Ok(Value::Number(Numeric::Decimal(d))),
+                Err(_) => Err(self.coercion_failed(value)),
+            },
+            _ => match self.parser.parse(s) {
+                Ok(parsed @ Value::Number(_)) => {
+                    self.convert_numeric_reporting(&parsed, to_ntype, losses)
+                }
+                _ => Err(self.coercion_failed(value)),
+            },
+        })
+    }
+
+    /// Converts a numeric (or textual) value to a specific numeric sub-type.
+    ///
+    /// Integers, decimals and reals without an imaginary part convert to
+    /// each other if the result converts back to the same number. Otherwise,
+    /// the `Lossy` policy rounds to integers with the rounding mode, and
+    /// converts to the nearest real number.
+    pub fn convert_numeric(
+        &self,
+        value: &Value,
+        to_ntype: &NumericType,
+    ) -> Result<Value, CoercionError> {
+        self.convert_numeric_reporting(value, to_ntype, &mut Vec::new())
+    }
+
+    /// Like `convert_numeric`, but adds the changes to the value to `losses`.
+    pub fn convert_numeric_reporting(
+        &self,
+        value: &Value,
+        to_ntype: &NumericType,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        let n = match value {
+            Value::Number(n) => n,
+            Value::Text(_) => return self.text_to_numeric(value, to_ntype, losses),
+            _ => return Err(CoercionError::UnexpectedType),
+        };
+
+        let (result, loss) = match to_ntype {
+            t if n.numeric_type() == *t => (Some(n.clone()), None),
+            NumericType::Integer | NumericType::Unsigned | NumericType::BigInteger => {
+                let (integer, loss) = match n.to_bigint() {
+                    Some(b) => (Some(b), None),
+                    None => (self.rounding.round(n), Some(LossKind::Rounded)),
+                };
+                let result = integer.and_then(|b| match to_ntype {
+                    NumericType::Integer => b.to_i64().map(Numeric::Integer),
+                    NumericType::Unsigned => b.to_u64().map(Numeric::Unsigned),
+                    _ => Some(Numeric::BigInteger(b)),
+                });
+                (result, loss)
+            }
+            NumericType::Real | NumericType::Complex => match nearest_real(n) {
+                Some((r, exact)) => {
+                    let result = match to_ntype {
+                        NumericType::Real => Numeric::Real(r),
+                        _ => Numeric::Complex(r, 0.0),
+                    };
+                    (Some(result), Some(LossKind::Precision).filter(|_| !exact))
+                }
+                None => (None, None),
+            },
+            NumericType::Decimal => {
+                let result = match n.as_decimal() {
+                    Some(d) => Some(Numeric::Decimal(d)),
+                    // The shortest textual representation of the f64 is used,
+                    // so that e.g. 0.1 becomes the decimal 0.1 and not its
+                    // binary expansion.
+                    None => n
+                        .as_real()
+                        .filter(|r| r.is_finite())
+                        .and_then(|r| BigDecimal::from_str(&r.to_string()).ok())
+                        .map(Numeric::Decimal),
+                };
+                (result, None)
+            }
+        };
+
+        match (result, loss) {
+            (Some(n), None) => Ok(Value::Number(n)),
+            (Some(n), Some(kind)) => self.lose(kind, value, Value::Number(n), losses),
+            (None, _) => Err(self.coercion_failed(value)),
+        }
+    }
+
+    /// Converts a date time (or text) to a specific date time sub-type.
+    ///
+    /// Under the `Lossy` policy, full date times are truncated to their
+    /// date or their time. The date is the one on the wall clock.
+    pub fn convert_datetime(
+        &self,
+        value: &Value,
+        to_dtype: &DateTimeType,
+    ) -> Result<Value, CoercionError> {
+        self.convert_datetime_reporting(value, to_dtype, &mut Vec::new())
+    }
+
+    /// Like `convert_datetime`, but adds the changes to the value to
+    /// `losses`.
+    pub fn convert_datetime_reporting(
+        &self,
+        value: &Value,
+        to_dtype: &DateTimeType,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        let datetime = match value {
+            Value::DateTime(datetime) => datetime,
+            Value::Text(_) => {
+                let parsed = self.text_to_datetime(value, losses)?;
+                return self.convert_datetime_reporting(&parsed, to_dtype, losses);
+            }
+            _ => return Err(CoercionError::UnexpectedType),
+        };
+
+        match (datetime, to_dtype) {
+            (datetime, t) if datetime.datetime_type() == *t => Ok(value.clone()),
+            (DateTime::Full { date, .. }, DateTimeType::Date) => self.lose(
+                LossKind::TruncatedTime,
+                value,
+                Value::DateTime(DateTime::Date(date.clone())),
+                losses,
+            ),
+            (DateTime::Full { time, .. }, DateTimeType::Time) => self.lose(
+                LossKind::TruncatedDate,
+                value,
+                Value::DateTime(DateTime::Time(time.clone())),
+                losses,
+            ),
+            _ => Err(CoercionError::CoercionFailed {
+                target_type: ValueType::DateTime,
+                source_value: value.clone(),
+            }),
+        }
+    }
+
+    /// Converts the items of an array, or the values of an object, to
+    /// `to_vtype`. Text is read as JSON first. Missing items are kept.
+    pub fn convert_elements(
+        &self,
+        value: &Value,
+        to_vtype: &ValueType,
+    ) -> Result<Value, CoercionError> {
+        self.convert_elements_reporting(value, to_vtype, &mut Vec::new())
+    }
+
+    /// Like `convert_elements`, but adds the changes to the items to
+    /// `losses`.
+    pub fn convert_elements_reporting(
+        &self,
+        value: &Value,
+        to_vtype: &ValueType,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        let mut convert = |item: &Value| match item {
+            Value::Missing(_) => Ok(item.clone()),
+            _ => self.convert_reporting(item, to_vtype, losses),
+        };
+        match value {
+            Value::Composite(Collection::Array(items)) => items
+                .iter()
+                .map(&mut convert)
+                .collect::<Result<_, _>>()
+                .map(|items| Value::Composite(Collection::Array(items))),
+            Value::Composite(Collection::Object(entries)) => entries
+                .iter()
+                .map(|(key, item)| Ok((key.clone(), convert(item)?)))
+                .collect::<Result<_, _>>()
+                .map(|entries| Value::Composite(Collection::Object(entries))),
+            Value::Text(_) => {
+                let composite = self.text_to_composite(value, losses)?;
+                self.convert_elements_reporting(&composite, to_vtype, losses)
+            }
             _ => Err(CoercionError::UnexpectedType),
         }
     }
@@ -48,12 +448,212 @@ impl Coercion {
 
     fn datetime_to_text(&self, value: &Value) -> Result<Value, CoercionError> {
         if let Value::DateTime(t) = value {
-            Ok(Value::Text(t.to_string()))
+            match &self.datetime_format {
+                Some(format) => match format.format(t) {
+                    Ok(text) => Ok(Value::Text(text)),
+                    Err(_) => Err(CoercionError::CoercionFailed {
+                        target_type: ValueType::Text,
+                        source_value: value.clone(),
+                    }),
+                },
+                None => Ok(Value::Text(t.to_string())),
+            }
         } else {
             Err(CoercionError::UnexpectedType)
         }
     }
 
+    fn duration_to_text(&self, value: &Value) -> Result<Value, CoercionError> {
+        if let Value::Duration(d) = value {
+            Ok(Value::Text(d.to_string()))
+        } else {
+            Err(CoercionError::UnexpectedType)
+        }
+    }
+
+    fn text_failed(&self, value: &Value, target_type: ValueType) -> CoercionError {
+        CoercionError::CoercionFailed {
+            target_type,
+            source_value: value.clone(),
+        }
+    }
+
+    /// Reads number literals, e.g. `42`, `-1.5e3` or `1+2i`.
+    fn text_to_number(
+        &self,
+        value: &Value,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        self.convert_text(value, losses, |s, _| match self.parser.parse(s) {
+            Ok(number @ Value::Number(_)) => Ok(number),
+            _ => Err(self.text_failed(value, ValueType::Number)),
+        })
+    }
+
+    /// Reads `true` and `false`, ignoring case.
+    fn text_to_boolean(
+        &self,
+        value: &Value,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        self.convert_text(value, losses, |s, _| {
+            if s.eq_ignore_ascii_case("true") {
+                Ok(Value::Boolean(true))
+            } else if s.eq_ignore_ascii_case("false") {
+                Ok(Value::Boolean(false))
+            } else {
+                Err(self.text_failed(value, ValueType::Boolean))
+            }
+        })
+    }
+
+    /// Reads date times with the date time format, if any, or in ISO 8601
+    /// format.
+    fn text_to_datetime(
+        &self,
+        value: &Value,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        self.convert_text(value, losses, |s, _| {
+            if let Some(Ok(datetime)) = self.datetime_format.as_ref().map(|f| f.parse(s)) {
+                return Ok(Value::DateTime(datetime));
+            }
+            iso8601_to_dk_value(s).map_err(|_| self.text_failed(value, ValueType::DateTime))
+        })
+    }
+
+    /// Reads JSON arrays and objects.
+    fn text_to_composite(
+        &self,
+        value: &Value,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        self.convert_text(value, losses, |s, _| match self.parser.parse(s) {
+            Ok(composite @ Value::Composite(_)) => Ok(composite),
+            _ => Err(self.text_failed(value, ValueType::Composite)),
+        })
+    }
+
+    /// Writes composites as JSON text. Missing values become `null`, and
+    /// date times, durations and complex numbers become strings.
+    fn composite_to_text(&self, value: &Value) -> Result<Value, CoercionError> {
+        match value {
+            Value::Composite(_) => {
+                let mut text = String::new();
+                self.write_json(value, &mut text)?;
+                Ok(Value::Text(text))
+            }
+            _ => Err(CoercionError::UnexpectedType),
+        }
+    }
+
+    /// Writes JSON text without going through `serde_json::Number`, which
+    /// would round decimals and big integers to `f64` unless `serde_json`
+    /// has arbitrary precision enabled.
+    fn write_json(&self, value: &Value, out: &mut String) -> Result<(), CoercionError> {
+        let string = |s: &str| serde_json::Value::String(s.to_string()).to_string();
+        match value {
+            Value::Missing(_) => out.push_str("null"),
+            Value::Boolean(b) => out.push_str(&b.to_string()),
+            Value::Text(s) => out.push_str(&string(s)),
+            Value::Number(Numeric::Real(r)) if !r.is_finite() => {
+                return Err(self.text_failed(value, ValueType::Text))
+            }
+            Value::Number(Numeric::Complex(_, _)) | Value::DateTime(_) | Value::Duration(_) => {
+                match self.convert(value, &ValueType::Text)? {
+                    Value::Text(text) => out.push_str(&string(&text)),
+                    _ => return Err(CoercionError::UnexpectedType),
+                }
+            }
+            Value::Number(n) => out.push_str(&n.to_string()),
+            Value::Composite(Collection::Array(items)) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    self.write_json(item, out)?;
+                }
+                out.push(']');
+            }
+            Value::Composite(Collection::Object(entries)) => {
+                out.push('{');
+                for (i, (key, item)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&string(key));
+                    out.push(':');
+                    self.write_json(item, out)?;
+                }
+                out.push('}');
+            }
+        }
+        Ok(())
+    }
+
+    fn text_to_duration(
+        &self,
+        value: &Value,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        self.convert_text(value, losses, |s, _| match Duration::from_str(s) {
+            Ok(d) => Ok(Value::Duration(d)),
+            Err(_) => Err(self.text_failed(value, ValueType::Duration)),
+        })
+    }
+
+    /// Converts a Unix timestamp to a full date time in UTC. Fractions of a
+    /// nanosecond are rounded.
+    fn number_to_datetime(&self, value: &Value) -> Result<Value, CoercionError> {
+        let n = match value {
+            Value::Number(n) => n,
+            _ => return Err(CoercionError::UnexpectedType),
+        };
+
+        let per_unit = self.epoch_unit.nanos();
+        let nanos = if let Some(b) = n.to_bigint() {
+            (b * per_unit).to_i128()
+        } else if let Some(d) = n.as_decimal() {
+            (d * BigDecimal::from(per_unit)).round(0).to_i128()
+        } else {
+            n.as_real()
+                .map(|r| (r * per_unit as f64).round())
+                .filter(|r| r.is_finite() && r.abs() < i128::MAX as f64)
+                .map(|r| r as i128)
+        };
+
+        match nanos.and_then(DateTime::from_epoch_nanos) {
+            Some(datetime) => Ok(Value::DateTime(datetime)),
+            None => Err(CoercionError::CoercionFailed {
+                target_type: ValueType::DateTime,
+                source_value: value.clone(),
+            }),
+        }
+    }
+
+    /// Converts a full date time to a Unix timestamp. The result is an
+    /// integer if the date time falls on a whole unit, and an exact decimal
+    /// otherwise.
+    fn datetime_to_number(&self, value: &Value) -> Result<Value, CoercionError> {
+        let nanos = match value {
+            Value::DateTime(datetime) => datetime.epoch_nanos(),
+            _ => return Err(CoercionError::UnexpectedType),
+        };
+
+        let per_unit = self.epoch_unit.nanos() as i128;
+        match nanos {
+            Some(nanos) if nanos % per_unit == 0 => Ok(Value::Number(Numeric::from_bigint(
+                BigInt::from(nanos / per_unit),
+            ))),
+            Some(nanos) => Ok(Value::Number(Numeric::Decimal(
+                BigDecimal::new(BigInt::from(nanos), self.epoch_unit.nanosecond_scale())
+                    .normalized(),
+            ))),
+            None => Err(self.coercion_failed(value)),
+        }
+    }
+
     fn boolean_to_number(&self, value: &Value) -> Result<Value, CoercionError> {
         if let Value::Boolean(b) = value {
             match b {
@@ -80,38 +680,57 @@ impl Coercion {
     }
 }
 
+/// Returns the nearest real number, and whether it converts back to `n`.
+/// Complex numbers are only converted if their imaginary part is zero.
+fn nearest_real(n: &Numeric) -> Option<(f64, bool)> {
+    let r = n.as_real()?;
+    let exact = match n {
+        Numeric::Real(_) | Numeric::Complex(_, _) => true,
+        _ if !r.is_finite() => return None,
+        Numeric::Decimal(d) => BigDecimal::from_str(&r.to_string()).is_ok_and(|back| back == *d),
+        _ => BigInt::from_f64(r) == n.to_bigint(),
+    };
+    Some((r, exact))
+}
+
 impl CoercesValues for Coercion {
     fn convert(&self, value: &Value, to_vtype: &ValueType) -> Result<Value, CoercionError> {
+        self.convert_reporting(value, to_vtype, &mut Vec::new())
+    }
+}
+
+impl Coercion {
+    /// Like `convert`, but adds the changes to the value to `losses`.
+    pub fn convert_reporting(
+        &self,
+        value: &Value,
+        to_vtype: &ValueType,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
         use ValueType::*;
 
         match (value.get_value_type(), to_vtype) {
-            (Number, Number) => Ok(value.clone()), // TODO deal with sub-types
+            // Sub-types are converted by `convert_numeric`.
+            (Number, Number) => Ok(value.clone()),
             (DateTime, DateTime) => Ok(value.clone()),
+            (Duration, Duration) => Ok(value.clone()),
             (Boolean, Boolean) => Ok(value.clone()),
             (Text, Text) => Ok(value.clone()),
             (Composite, Composite) => Ok(value.clone()),
-            (Text, Composite) => Err(CoercionError::CoercionImpossible {
-                from: ValueType::Text,
-                to: ValueType::Composite,
-            }),
+            (Text, Composite) => self.text_to_composite(value, losses),
+            (Composite, Text) => self.composite_to_text(value),
             (Number, Text) => self.number_to_text(value),
             (Boolean, Text) => self.boolean_to_text(value),
             (DateTime, Text) => self.datetime_to_text(value),
+            (Duration, Text) => self.duration_to_text(value),
+            (Text, Number) => self.text_to_number(value, losses),
+            (Text, Boolean) => self.text_to_boolean(value, losses),
+            (Text, DateTime) => self.text_to_datetime(value, losses),
+            (Text, Duration) => self.text_to_duration(value, losses),
             (Number, Boolean) => self.number_to_boolean(value),
             (Boolean, Number) => self.boolean_to_number(value),
-            (Text, b) => match value {
-                Value::Text(s) => match self.parser.parse(s) {
-                    Err(_) => Err(CoercionError::CoercionFailed {
-                        target_type: b.clone(),
-                        source_value: value.clone(),
-                    }),
-                    Ok(x) => Ok(x),
-                },
-                _ => Err(CoercionError::CoercionImpossible {
-                    from: Text,
-                    to: b.clone(),
-                }),
-            },
+            (Number, DateTime) => self.number_to_datetime(value),
+            (DateTime, Number) => self.datetime_to_number(value),
             (a, Missing) => Err(CoercionError::CoercionImpossible {
                 from: a.clone(),
                 to: ValueType::Missing,
@@ -136,6 +755,14 @@ impl CoercesValues for Coercion {
                 from: ValueType::DateTime,
                 to: b.clone(),
             }),
+            (a, Duration) => Err(CoercionError::CoercionImpossible {
+                from: a.clone(),
+                to: ValueType::Duration,
+            }),
+            (Duration, b) => Err(CoercionError::CoercionImpossible {
+                from: ValueType::Duration,
+                to: b.clone(),
+            }),
         }
     }
 }
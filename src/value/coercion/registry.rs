@@ -0,0 +1,112 @@
+//! User-defined coercion rules.
+
+use super::{Coercion, CoercionLoss};
+use crate::errors::CoercionError;
+use crate::value::definitions::*;
+use crate::value::traits::*;
+use std::collections::HashMap;
+
+type Rule = Box<dyn CoercesValues + Send + Sync>;
+
+/// Holds coercion rules for conversions that `Coercion` does not know,
+/// e.g. `"Y"` and `"N"` to booleans.
+///
+/// Rules are either keyed by their source and target `ValueType`, in which
+/// case `convert` uses them instead of the fallback `Coercion`, or by a
+/// name, under which they can be referenced by a `ColumnContract`.
+pub struct CoercionRegistry {
+    fallback: Coercion,
+    by_types: HashMap<(ValueType, ValueType), Rule>,
+    by_name: HashMap<String, Rule>,
+}
+
+impl Default for CoercionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoercionRegistry {
+    pub fn new() -> Self {
+        Self {
+            fallback: Coercion::new(),
+            by_types: HashMap::new(),
+            by_name: HashMap::new(),
+        }
+    }
+
+    /// Coercion for conversions without a rule. Defaults to
+    /// `Coercion::new()`.
+    pub fn with_fallback(mut self, fallback: Coercion) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Converts values of type `from` to `to` with `rule`, replacing any
+    /// previous rule for the two types.
+    pub fn with_rule<C>(mut self, from: ValueType, to: ValueType, rule: C) -> Self
+    where
+        C: CoercesValues + Send + Sync + 'static,
+    {
+        self.by_types.insert((from, to), Box::new(rule));
+        self
+    }
+
+    /// Registers `rule` under `name`, replacing any previous rule with the
+    /// same name.
+    pub fn with_named_rule<C>(mut self, name: &str, rule: C) -> Self
+    where
+        C: CoercesValues + Send + Sync + 'static,
+    {
+        self.by_name.insert(name.to_string(), Box::new(rule));
+        self
+    }
+
+    pub fn fallback(&self) -> &Coercion {
+        &self.fallback
+    }
+
+    pub fn rule(&self, name: &str) -> Option<&dyn CoercesValues> {
+        self.by_name
+            .get(name)
+            .map(|rule| rule.as_ref() as &dyn CoercesValues)
+    }
+
+    /// Converts `value` with the rule registered under `name`.
+    pub fn convert_with_rule(
+        &self,
+        name: &str,
+        value: &Value,
+        to_vtype: &ValueType,
+    ) -> Result<Value, CoercionError> {
+        match self.rule(name) {
+            Some(rule) => rule.convert(value, to_vtype),
+            None => Err(CoercionError::UnknownRule(name.to_string())),
+        }
+    }
+}
+
+impl CoercesValues for CoercionRegistry {
+    fn convert(&self, value: &Value, to_vtype: &ValueType) -> Result<Value, CoercionError> {
+        self.convert_reporting(value, to_vtype, &mut Vec::new())
+    }
+}
+
+impl CoercionRegistry {
+    /// Like `convert`, but adds the changes made by the fallback to
+    /// `losses`. Rules do not report their changes.
+    pub fn convert_reporting(
+        &self,
+        value: &Value,
+        to_vtype: &ValueType,
+        losses: &mut Vec<CoercionLoss>,
+    ) -> Result<Value, CoercionError> {
+        match self
+            .by_types
+            .get(&(value.get_value_type().clone(), to_vtype.clone()))
+        {
+            Some(rule) => rule.convert(value, to_vtype),
+            None => self.fallback.convert_reporting(value, to_vtype, losses),
+        }
+    }
+}
@@ -1,7 +1,11 @@
 use crate::errors::*;
+use crate::value::clock::*;
 use crate::value::definitions::*;
+use crate::value::path::pointer_segment;
+use crate::value::primitives::*;
 use crate::value::traits::ValidatesValues;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 
 macro_rules! _to_valueconstraint_err {
     ( $($value:expr, $constraint:expr)? ) => {
@@ -16,34 +20,345 @@ macro_rules! _to_valueconstraint_err {
     };
 }
 
+// Complex numbers have no natural order, so bounds are only applicable to
+// complex values without an imaginary part.
+fn is_non_real_complex(value: &Value) -> bool {
+    matches!(value, Value::Number(Numeric::Complex(_, im)) if *im != 0.0)
+}
+
+// Full date times are converted to UTC, other values are left as they are.
+// So are date times that cannot be converted, e.g. because of an unknown zone.
+fn normalize_to_utc(value: &Value) -> Value {
+    match value {
+        Value::DateTime(datetime @ DateTime::Full { .. }) => match datetime.to_utc() {
+            Some(normalized) => Value::DateTime(normalized),
+            None => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+fn normalize_constraint_to_utc(constraint: &ValueConstraint) -> ValueConstraint {
+    match constraint {
+        ValueConstraint::Not(c) => ValueConstraint::Not(Box::new(normalize_constraint_to_utc(c))),
+        ValueConstraint::OneOf(values) => {
+            ValueConstraint::OneOf(values.iter().map(normalize_to_utc).collect())
+        }
+        ValueConstraint::Maximum(max) => ValueConstraint::Maximum(normalize_to_utc(max)),
+        ValueConstraint::Minimum(min) => ValueConstraint::Minimum(normalize_to_utc(min)),
+        ValueConstraint::NormalizedToUtc(c) => normalize_constraint_to_utc(c),
+        other => other.clone(),
+    }
+}
+
+/// A point in time for temporal constraints, either fixed or relative to the
+/// current time of a `Clock`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TemporalBound {
+    At(DateTime),
+    Now,
+    /// The current time shifted by a duration, e.g. `-P90D` for 90 days ago.
+    FromNow(Duration),
+}
+
+impl TemporalBound {
+    fn resolve(&self, clock: &dyn Clock) -> Option<DateTime> {
+        match self {
+            TemporalBound::At(datetime) => Some(datetime.clone()),
+            TemporalBound::Now => Some(clock.now()),
+            TemporalBound::FromNow(duration) => clock.now().checked_add(duration),
+        }
+    }
+}
+
+// Compares instants for full date times and days for dates. A full date time
+// compared with a date is reduced to its date in UTC. Times can only be
+// compared with times in fixed offsets.
+fn temporal_cmp(value: &DateTime, bound: &DateTime) -> Option<Ordering> {
+    let day = (SECONDS_PER_DAY * NANOS_PER_SECOND) as i128;
+    let utc_days = |datetime: &DateTime| match datetime {
+        DateTime::Date(date) => date.to_epoch_days().map(|days| days as i128),
+        _ => datetime.epoch_nanos().map(|nanos| nanos.div_euclid(day)),
+    };
+    let utc_time = |time: &Time| {
+        let offset = time.timezone.fixed_offset_seconds()?;
+        Some(time.nanos_of_day() - offset * NANOS_PER_SECOND)
+    };
+
+    match (value, bound) {
+        (DateTime::Full { .. }, DateTime::Full { .. }) => {
+            value.epoch_nanos()?.partial_cmp(&bound.epoch_nanos()?)
+        }
+        (DateTime::Time(a), DateTime::Time(b)) => utc_time(a)?.partial_cmp(&utc_time(b)?),
+        (DateTime::Time(_), _) | (_, DateTime::Time(_)) => None,
+        _ => utc_days(value)?.partial_cmp(&utc_days(bound)?),
+    }
+}
+
+// ISO weekday of the calendar date of a date or full date time.
+fn weekday_of(datetime: &DateTime) -> Option<DayNumber> {
+    match datetime {
+        DateTime::Date(date) | DateTime::Full { date, .. } => match date.to_year_week_day()? {
+            Date::YearWeekDay { day_in_week, .. } => Some(day_in_week),
+            _ => None,
+        },
+        DateTime::Time(_) => None,
+    }
+}
+
 // Contracts & Constraints
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TypeConstraint {
     IsType(ValueType),
+    /// An array whose items all satisfy the constraint.
+    ArrayOf(Box<TypeConstraint>),
+    /// An object with the given fields. Absent fields are checked as missing
+    /// values, and other fields are allowed.
+    Object(Vec<(String, TypeConstraint)>),
+    /// An object whose values all satisfy the constraint.
+    MapOf(Box<TypeConstraint>),
 }
 
-impl ValidatesValues for TypeConstraint {
-    fn validate(&self, value: &Value) -> Result<(), ValidationError> {
-        match (self, value.get_value_type()) {
-            (TypeConstraint::IsType(expected), received) => {
-                if expected == received {
-                    Ok(())
-                } else {
-                    Err(ValidationError::ValueValidationError {
-                        offending_value: value.clone(),
-                        failed_constraints: vec![ConstraintError::TypeError {
-                            expected: expected.clone(),
-                            received: received.clone(),
-                        }],
+impl TypeConstraint {
+    /// Returns the type of the value itself, i.e. `Composite` for arrays and
+    /// objects.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            TypeConstraint::IsType(value_type) => value_type.clone(),
+            _ => ValueType::Composite,
+        }
+    }
+
+    /// Adds the type errors of `value` and of the values inside it to
+    /// `errors`. Errors inside composites are reported at their JSON
+    /// Pointer.
+    fn type_errors(&self, value: &Value, path: &str, errors: &mut Vec<ConstraintError>) {
+        let mut report = |error| {
+            errors.push(match path {
+                "" => error,
+                _ => ConstraintError::AtPath {
+                    path: path.to_string(),
+                    error: Box::new(error),
+                },
+            })
+        };
+
+        let received = value.get_value_type();
+        let expected = self.value_type();
+        if *received != expected {
+            report(ConstraintError::TypeError {
+                expected,
+                received: received.clone(),
+            });
+            return;
+        }
+
+        match (self, value) {
+            (TypeConstraint::IsType(_), _) => {}
+            (TypeConstraint::ArrayOf(item), Value::Composite(Collection::Array(items))) => {
+                for (index, value) in items.iter().enumerate() {
+                    item.type_errors(value, &format!("{}/{}", path, index), errors);
+                }
+            }
+            (TypeConstraint::MapOf(item), Value::Composite(Collection::Object(entries))) => {
+                for (key, value) in entries.iter() {
+                    let path = format!("{}/{}", path, pointer_segment(key));
+                    item.type_errors(value, &path, errors);
+                }
+            }
+            (TypeConstraint::Object(fields), Value::Composite(Collection::Object(entries))) => {
+                let absent = Value::Missing(Empty::Expected);
+                for (name, field) in fields.iter() {
+                    let value = entries
+                        .iter()
+                        .find(|(key, _)| key == name)
+                        .map_or(&absent, |(_, value)| value);
+                    let path = format!("{}/{}", path, pointer_segment(name));
+                    field.type_errors(value, &path, errors);
+                }
+            }
+            // An array where an object is expected, or the other way round.
+            _ => report(ConstraintError::StructureError {
+                expected: self.clone(),
+            }),
+        }
+    }
+
+    /// Infers the most specific constraint that `value` satisfies. Objects
+    /// are inferred as `Object`, never as `MapOf`.
+    pub fn infer(value: &Value) -> TypeConstraint {
+        match value {
+            Value::Composite(Collection::Array(items)) => {
+                let mut present = items
+                    .iter()
+                    .filter(|item| !matches!(item, Value::Missing(_)));
+                match present.next() {
+                    Some(first) => present
+                        .try_fold(TypeConstraint::infer(first), |inferred, item| {
+                            inferred.merge(&TypeConstraint::infer(item))
+                        })
+                        .map_or(TypeConstraint::IsType(ValueType::Composite), |item| {
+                            TypeConstraint::ArrayOf(Box::new(item))
+                        }),
+                    None => TypeConstraint::IsType(ValueType::Composite),
+                }
+            }
+            Value::Composite(Collection::Object(entries)) => TypeConstraint::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.clone(), TypeConstraint::infer(value)))
+                    .collect(),
+            ),
+            value => TypeConstraint::IsType(value.get_value_type().clone()),
+        }
+    }
+
+    /// Infers the constraint that all non-missing `values` satisfy, if they
+    /// have the same type.
+    pub fn infer_all(values: &[Value]) -> Option<TypeConstraint> {
+        let mut present = values
+            .iter()
+            .filter(|value| !matches!(value, Value::Missing(_)));
+        let first = TypeConstraint::infer(present.next()?);
+        present.try_fold(first, |inferred, value| {
+            inferred.merge(&TypeConstraint::infer(value))
+        })
+    }
+
+    /// The most specific constraint that values of both constraints satisfy,
+    /// if their value types agree. Objects keep their common fields.
+    fn merge(&self, other: &TypeConstraint) -> Option<TypeConstraint> {
+        use TypeConstraint::*;
+
+        if self == other {
+            return Some(self.clone());
+        }
+        match (self, other) {
+            (ArrayOf(a), ArrayOf(b)) => Some(
+                a.merge(b)
+                    .map_or(IsType(ValueType::Composite), |item| ArrayOf(Box::new(item))),
+            ),
+            (Object(a), Object(b)) => Some(Object(
+                a.iter()
+                    .filter_map(|(name, field)| {
+                        let (_, other) = b.iter().find(|(other, _)| other == name)?;
+                        field.merge(other).map(|field| (name.clone(), field))
                     })
+                    .collect(),
+            )),
+            (MapOf(a), MapOf(b)) => Some(
+                a.merge(b)
+                    .map_or(IsType(ValueType::Composite), |item| MapOf(Box::new(item))),
+            ),
+            // Empty arrays are not more specific than any array.
+            (ArrayOf(_), IsType(ValueType::Composite)) => Some(self.clone()),
+            (IsType(ValueType::Composite), ArrayOf(_)) => Some(other.clone()),
+            (a, b) if a.value_type() == b.value_type() => Some(IsType(a.value_type())),
+            _ => None,
+        }
+    }
+
+    /// Maps the constraint to a JSON Schema. Date times are strings, and
+    /// durations strings in the `duration` format.
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        match self {
+            TypeConstraint::IsType(value_type) => match value_type {
+                ValueType::Number => serde_json::json!({"type": "number"}),
+                ValueType::Boolean => serde_json::json!({"type": "boolean"}),
+                ValueType::Text | ValueType::DateTime => serde_json::json!({"type": "string"}),
+                ValueType::Duration => {
+                    serde_json::json!({"type": "string", "format": "duration"})
+                }
+                ValueType::Missing => serde_json::json!({"type": "null"}),
+                ValueType::Composite => serde_json::json!({"type": ["array", "object"]}),
+            },
+            TypeConstraint::ArrayOf(item) => {
+                serde_json::json!({"type": "array", "items": item.to_json_schema()})
+            }
+            TypeConstraint::Object(fields) => {
+                let properties: serde_json::Map<String, serde_json::Value> = fields
+                    .iter()
+                    .map(|(name, field)| (name.clone(), field.to_json_schema()))
+                    .collect();
+                let required: Vec<&String> = fields.iter().map(|(name, _)| name).collect();
+                serde_json::json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                })
+            }
+            TypeConstraint::MapOf(item) => serde_json::json!({
+                "type": "object",
+                "additionalProperties": item.to_json_schema(),
+            }),
+        }
+    }
+
+    /// Reads a constraint from a JSON Schema produced by `to_json_schema`,
+    /// or a similar one. Integers are numbers, and strings in the
+    /// `date-time`, `date` or `time` formats are date times.
+    pub fn from_json_schema(schema: &serde_json::Value) -> Option<TypeConstraint> {
+        let is_type = |value_type| Some(TypeConstraint::IsType(value_type));
+        let types: Vec<&str> = match schema.get("type")? {
+            serde_json::Value::String(name) => vec![name.as_str()],
+            serde_json::Value::Array(names) => names
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Option<_>>()?,
+            _ => return None,
+        };
+        match types.as_slice() {
+            ["number"] | ["integer"] => is_type(ValueType::Number),
+            ["boolean"] => is_type(ValueType::Boolean),
+            ["null"] => is_type(ValueType::Missing),
+            ["string"] => match schema.get("format").and_then(|format| format.as_str()) {
+                Some("date-time") | Some("date") | Some("time") => is_type(ValueType::DateTime),
+                Some("duration") => is_type(ValueType::Duration),
+                _ => is_type(ValueType::Text),
+            },
+            ["array"] => match schema.get("items") {
+                Some(items) => Some(TypeConstraint::ArrayOf(Box::new(
+                    TypeConstraint::from_json_schema(items)?,
+                ))),
+                None => is_type(ValueType::Composite),
+            },
+            ["object"] => {
+                if let Some(properties) = schema.get("properties") {
+                    let fields = properties
+                        .as_object()?
+                        .iter()
+                        .map(|(name, field)| {
+                            Some((name.clone(), TypeConstraint::from_json_schema(field)?))
+                        })
+                        .collect::<Option<_>>()?;
+                    Some(TypeConstraint::Object(fields))
+                } else if let Some(item @ serde_json::Value::Object(_)) =
+                    schema.get("additionalProperties")
+                {
+                    Some(TypeConstraint::MapOf(Box::new(
+                        TypeConstraint::from_json_schema(item)?,
+                    )))
+                } else {
+                    is_type(ValueType::Composite)
                 }
             }
+            ["array", "object"] | ["object", "array"] => is_type(ValueType::Composite),
+            _ => None,
         }
     }
 }
 
+impl ValidatesValues for TypeConstraint {
+    fn validate(&self, value: &Value) -> Result<(), ValidationError> {
+        let mut errors = Vec::new();
+        self.type_errors(value, "", &mut errors);
+        collect_errors(value, errors)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ValueConstraint {
@@ -52,18 +367,130 @@ pub enum ValueConstraint {
     OneOf(Vec<Value>),
     Maximum(Value),
     Minimum(Value),
+    /// The length of text, or the number of items of an array or object.
     MaximumLength(usize),
     MinimumLength(usize),
+    MultipleOf(Value),
+    /// Converts full date times in the value and in the wrapped constraint to
+    /// UTC before validating, so that e.g. `Maximum` compares instants rather
+    /// than wall clock times in different zones.
+    NormalizedToUtc(Box<ValueConstraint>),
+    /// The date time is at or after the bound.
+    NotBefore(TemporalBound),
+    /// The date time is at or before the bound.
+    NotAfter(TemporalBound),
+    /// The date falls on one of the ISO weekdays, from 1 (Monday) to 7
+    /// (Sunday). Full date times are checked on their wall clock date.
+    Weekdays(Vec<DayNumber>),
+    /// The date falls on a day from Monday to Friday that is not a holiday.
+    BusinessDay {
+        holidays: Vec<Date>,
+    },
+    /// The value is a date without a time.
+    DateOnly,
+    /// The value has a time in a specified time zone.
+    HasTimeZone,
+    /// No two items of the array are equal.
+    UniqueItems,
+    /// Every item of the array satisfies the contract.
+    EveryItem(Box<ValueContract>),
+    /// The object has all the keys.
+    RequiredKeys(Vec<String>),
+    /// The values of the object satisfy the contracts of their keys. Absent
+    /// keys are not checked.
+    Properties {
+        properties: Vec<(String, ValueContract)>,
+        additional: AdditionalProperties,
+    },
+}
+
+/// What `ValueConstraint::Properties` does with keys it has no contract for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AdditionalProperties {
+    Allow,
+    Forbid,
+    /// Their values satisfy the contract.
+    Satisfy(Box<ValueContract>),
+}
+
+/// Moves an error of a value inside a composite to `path`, below any path
+/// it already has.
+fn at_path(path: &str, error: ConstraintError) -> ConstraintError {
+    match error {
+        ConstraintError::AtPath { path: inner, error } => ConstraintError::AtPath {
+            path: format!("{}{}", path, inner),
+            error,
+        },
+        error => ConstraintError::AtPath {
+            path: path.to_string(),
+            error: Box::new(error),
+        },
+    }
+}
+
+/// The errors of validating a value inside a composite, at `path`.
+fn nested_errors(result: Result<(), ValidationError>, path: &str) -> Vec<ConstraintError> {
+    match result {
+        Ok(()) => Vec::new(),
+        Err(ValidationError::ValueValidationError {
+            failed_constraints, ..
+        }) => failed_constraints
+            .into_iter()
+            .map(|error| at_path(path, error))
+            .collect(),
+    }
+}
+
+fn item_count(value: &Value) -> Option<usize> {
+    match value {
+        Value::Text(text) => Some(text.len()),
+        Value::Composite(Collection::Array(items)) => Some(items.len()),
+        Value::Composite(Collection::Object(entries)) => Some(entries.len()),
+        _ => None,
+    }
 }
 
 impl ValidatesValues for ValueConstraint {
     fn validate(&self, value: &Value) -> Result<(), ValidationError> {
+        self.validate_with_clock(value, &SystemClock)
+    }
+}
+
+impl ValueConstraint {
+    /// Validates `value`, resolving bounds relative to the current time with
+    /// `clock`.
+    pub fn validate_with_clock(
+        &self,
+        value: &Value,
+        clock: &dyn Clock,
+    ) -> Result<(), ValidationError> {
+        let invalid_constraint = || {
+            Err(ValidationError::ValueValidationError {
+                offending_value: value.clone(),
+                failed_constraints: vec![ConstraintError::InvalidConstraintError],
+            })
+        };
+
         match (self, value) {
             (ValueConstraint::Any, _) => Ok(()),
-            (ValueConstraint::Not(c), _) => match c.validate(value) {
+            (ValueConstraint::Not(c), _) => match c.validate_with_clock(value, clock) {
                 Ok(()) => _to_valueconstraint_err!(value, self),
                 Err(_) => Ok(()),
             },
+            (ValueConstraint::NormalizedToUtc(c), _) => {
+                match normalize_constraint_to_utc(c)
+                    .validate_with_clock(&normalize_to_utc(value), clock)
+                {
+                    Ok(()) => Ok(()),
+                    Err(ValidationError::ValueValidationError {
+                        failed_constraints, ..
+                    }) => Err(ValidationError::ValueValidationError {
+                        offending_value: value.clone(),
+                        failed_constraints,
+                    }),
+                }
+            }
             (ValueConstraint::OneOf(allowed_values), _) => {
                 let mut is_one_of_the_allowed = false;
                 for allowed in allowed_values.iter() {
@@ -76,9 +503,17 @@ impl ValidatesValues for ValueConstraint {
                 if is_one_of_the_allowed {
                     Ok(())
                 } else {
-                    return _to_valueconstraint_err!(value, self);
+                    _to_valueconstraint_err!(value, self)
                 }
             }
+            (ValueConstraint::Maximum(bound), _) | (ValueConstraint::Minimum(bound), _)
+                if is_non_real_complex(value) || is_non_real_complex(bound) =>
+            {
+                Err(ValidationError::ValueValidationError {
+                    offending_value: value.clone(),
+                    failed_constraints: vec![ConstraintError::InvalidConstraintError],
+                })
+            }
             (ValueConstraint::Maximum(max), _) => {
                 if value <= max {
                     Ok(())
@@ -93,20 +528,32 @@ impl ValidatesValues for ValueConstraint {
                     _to_valueconstraint_err!(value, self)
                 }
             }
-            (ValueConstraint::MaximumLength(len), Value::Text(text)) => {
-                if text.len() <= *len {
+            (ValueConstraint::MaximumLength(len), _) if item_count(value).is_some() => {
+                if item_count(value) <= Some(*len) {
                     Ok(())
                 } else {
                     _to_valueconstraint_err!(value.clone(), self)
                 }
             }
-            (ValueConstraint::MinimumLength(len), Value::Text(text)) => {
-                if text.len() >= *len {
+            (ValueConstraint::MinimumLength(len), _) if item_count(value).is_some() => {
+                if item_count(value) >= Some(*len) {
                     Ok(())
                 } else {
                     _to_valueconstraint_err!(value.clone(), self)
                 }
             }
+            (ValueConstraint::MultipleOf(divisor), Value::Number(_)) => match value % divisor {
+                Ok(Value::Number(remainder)) if remainder.is_zero() => Ok(()),
+                Ok(Value::Number(_)) => _to_valueconstraint_err!(value, self),
+                _ => Err(ValidationError::ValueValidationError {
+                    offending_value: value.clone(),
+                    failed_constraints: vec![ConstraintError::InvalidConstraintError],
+                }),
+            },
+            (ValueConstraint::MultipleOf(_), _) => Err(ValidationError::ValueValidationError {
+                offending_value: value.clone(),
+                failed_constraints: vec![ConstraintError::InvalidConstraintError],
+            }),
             (ValueConstraint::MaximumLength(_), _) => Err(ValidationError::ValueValidationError {
                 offending_value: value.clone(),
                 failed_constraints: vec![ConstraintError::InvalidConstraintError],
@@ -115,10 +562,142 @@ impl ValidatesValues for ValueConstraint {
                 offending_value: value.clone(),
                 failed_constraints: vec![ConstraintError::InvalidConstraintError],
             }),
+            (ValueConstraint::NotBefore(bound), Value::DateTime(datetime))
+            | (ValueConstraint::NotAfter(bound), Value::DateTime(datetime)) => {
+                let ordering = match bound.resolve(clock) {
+                    Some(bound) => temporal_cmp(datetime, &bound),
+                    None => None,
+                };
+                match (self, ordering) {
+                    (_, None) => invalid_constraint(),
+                    (ValueConstraint::NotBefore(_), Some(Ordering::Less))
+                    | (ValueConstraint::NotAfter(_), Some(Ordering::Greater)) => {
+                        _to_valueconstraint_err!(value, self)
+                    }
+                    _ => Ok(()),
+                }
+            }
+            (ValueConstraint::Weekdays(weekdays), Value::DateTime(datetime)) => {
+                match weekday_of(datetime) {
+                    Some(weekday) if weekdays.contains(&weekday) => Ok(()),
+                    Some(_) => _to_valueconstraint_err!(value, self),
+                    None => invalid_constraint(),
+                }
+            }
+            (ValueConstraint::BusinessDay { holidays }, Value::DateTime(datetime)) => {
+                let date = match datetime {
+                    DateTime::Date(date) | DateTime::Full { date, .. } => date,
+                    DateTime::Time(_) => return invalid_constraint(),
+                };
+                match weekday_of(datetime) {
+                    Some(weekday) if weekday <= 5 && !holidays.contains(date) => Ok(()),
+                    Some(_) => _to_valueconstraint_err!(value, self),
+                    None => invalid_constraint(),
+                }
+            }
+            (ValueConstraint::DateOnly, Value::DateTime(DateTime::Date(_))) => Ok(()),
+            (ValueConstraint::HasTimeZone, Value::DateTime(DateTime::Time(time)))
+            | (ValueConstraint::HasTimeZone, Value::DateTime(DateTime::Full { time, .. })) => {
+                if time.timezone == TimeZone::Unspecified {
+                    _to_valueconstraint_err!(value, self)
+                } else {
+                    Ok(())
+                }
+            }
+            (ValueConstraint::DateOnly, Value::DateTime(_))
+            | (ValueConstraint::HasTimeZone, Value::DateTime(_)) => {
+                _to_valueconstraint_err!(value, self)
+            }
+            (ValueConstraint::UniqueItems, Value::Composite(Collection::Array(items))) => {
+                let errors: Vec<ConstraintError> = items
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, item)| items[..*index].contains(item))
+                    .map(|(index, _)| {
+                        at_path(
+                            &format!("/{}", index),
+                            ConstraintError::InvalidValueError(self.clone()),
+                        )
+                    })
+                    .collect();
+                collect_errors(value, errors)
+            }
+            (ValueConstraint::EveryItem(contract), Value::Composite(Collection::Array(items))) => {
+                let errors = items
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, item)| {
+                        nested_errors(
+                            contract.validate_with_clock(item, clock),
+                            &format!("/{}", index),
+                        )
+                    })
+                    .collect();
+                collect_errors(value, errors)
+            }
+            (
+                ValueConstraint::RequiredKeys(keys),
+                Value::Composite(Collection::Object(entries)),
+            ) => {
+                let errors = keys
+                    .iter()
+                    .filter(|key| !entries.iter().any(|(other, _)| other == *key))
+                    .map(|key| ConstraintError::MissingKey(key.clone()))
+                    .collect();
+                collect_errors(value, errors)
+            }
+            (
+                ValueConstraint::Properties {
+                    properties,
+                    additional,
+                },
+                Value::Composite(Collection::Object(entries)),
+            ) => {
+                let mut errors = Vec::new();
+                for (key, item) in entries.iter() {
+                    let path = format!("/{}", pointer_segment(key));
+                    let contract = properties.iter().find(|(name, _)| name == key);
+                    match (contract, additional) {
+                        (Some((_, contract)), _) => errors.extend(nested_errors(
+                            contract.validate_with_clock(item, clock),
+                            &path,
+                        )),
+                        (None, AdditionalProperties::Allow) => {}
+                        (None, AdditionalProperties::Forbid) => {
+                            errors.push(ConstraintError::UnexpectedKey(key.clone()))
+                        }
+                        (None, AdditionalProperties::Satisfy(contract)) => errors.extend(
+                            nested_errors(contract.validate_with_clock(item, clock), &path),
+                        ),
+                    }
+                }
+                collect_errors(value, errors)
+            }
+            (ValueConstraint::NotBefore(_), _)
+            | (ValueConstraint::NotAfter(_), _)
+            | (ValueConstraint::Weekdays(_), _)
+            | (ValueConstraint::BusinessDay { .. }, _)
+            | (ValueConstraint::DateOnly, _)
+            | (ValueConstraint::HasTimeZone, _)
+            | (ValueConstraint::UniqueItems, _)
+            | (ValueConstraint::EveryItem(_), _)
+            | (ValueConstraint::RequiredKeys(_), _)
+            | (ValueConstraint::Properties { .. }, _) => invalid_constraint(),
         }
     }
 }
 
+fn collect_errors(value: &Value, errors: Vec<ConstraintError>) -> Result<(), ValidationError> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError::ValueValidationError {
+            offending_value: value.clone(),
+            failed_constraints: errors,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ValueContract {
@@ -137,6 +716,17 @@ impl ValueContract {
 
 impl ValidatesValues for ValueContract {
     fn validate(&self, value: &Value) -> Result<(), ValidationError> {
+        self.validate_with_clock(value, &SystemClock)
+    }
+}
+
+impl ValueContract {
+    /// Validates `value`, resolving temporal bounds with `clock`.
+    pub fn validate_with_clock(
+        &self,
+        value: &Value,
+        clock: &dyn Clock,
+    ) -> Result<(), ValidationError> {
         let mut errors_found = false;
         let mut errors: Vec<ConstraintError> = Vec::new();
         if let Err(tce) = self.expected_type.validate(value) {
@@ -152,7 +742,7 @@ impl ValidatesValues for ValueContract {
         };
 
         for vc in self.value_constraints.iter() {
-            if let Err(vce) = vc.validate(value) {
+            if let Err(vce) = vc.validate_with_clock(value, clock) {
                 errors_found = true;
                 match vce {
                     ValidationError::ValueValidationError {
@@ -1,5 +1,7 @@
 use crate::value::primitives::*;
+use bigdecimal::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 macro_rules! value_type_definition {
     ( $( $i:ident($t:ty) ),+ ) => {
@@ -15,7 +17,7 @@ macro_rules! value_type_definition {
         }
 
         /// The type of a dynamic runtime value.
-        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
         #[serde(rename_all = "camelCase")]
         pub enum ValueType {
             $(
@@ -76,27 +78,54 @@ macro_rules! impl_from_value_to_t_option {
     };
 }
 
+macro_rules! impl_try_from_value_to_t_option {
+    ( $( $type:ty => $p:pat => $exp:expr ),+ ) => {
+
+        $(
+            impl From<Value> for Option<$type> {
+                fn from(value: Value) -> Self {
+                    match value {
+                        $p => $exp,
+                        _ => None,
+                    }
+                }
+            }
+        )+
+
+    };
+}
+
 value_type_definition! {
     Number(Numeric),
     Text(String),
     DateTime(DateTime),
+    Duration(Duration),
     Missing(Empty),
     Boolean(bool),
     Composite(Collection<Value>)
 }
 
 impl_from_t_to_value! {
-    i32 => |value: &i32| { Value::Number(Numeric::Integer(value.clone() as i64)) },
-    i64 => |value: &i64| { Value::Number(Numeric::Integer(value.clone())) },
-    f32 => |value: &f32| { Value::Number(Numeric::Real(value.clone() as f64))},
-    f64 => |value: &f64| { Value::Number(Numeric::Real(value.clone())) },
+    i32 => |value: &i32| { Value::Number(Numeric::Integer(*value as i64)) },
+    i64 => |value: &i64| { Value::Number(Numeric::Integer(*value)) },
+    u8 => |value: &u8| { Value::Number(Numeric::Integer(*value as i64)) },
+    u16 => |value: &u16| { Value::Number(Numeric::Integer(*value as i64)) },
+    u32 => |value: &u32| { Value::Number(Numeric::Integer(*value as i64)) },
+    u64 => |value: &u64| { Value::Number(Numeric::from_bigint(BigInt::from(*value))) },
+    usize => |value: &usize| { Value::Number(Numeric::from_bigint(BigInt::from(*value))) },
+    i128 => |value: &i128| { Value::Number(Numeric::from_bigint(BigInt::from(*value))) },
+    u128 => |value: &u128| { Value::Number(Numeric::from_bigint(BigInt::from(*value))) },
+    BigInt => |value: &BigInt| { Value::Number(Numeric::from_bigint(value.clone())) },
+    f32 => |value: &f32| { Value::Number(Numeric::Real(*value as f64))},
+    f64 => |value: &f64| { Value::Number(Numeric::Real(*value)) },
     (f64, f64) => |value: &(f64, f64)| {
         let real = value.0;
         let imaginary = value.1;
         Value::Number(Numeric::Complex(real, imaginary))
     },
+    BigDecimal => |value: &BigDecimal| { Value::Number(Numeric::Decimal(value.clone())) },
     String => |value: &String| {
-        if value.len() == 0 {
+        if value.is_empty() {
             Value::Missing(Empty::Unexpected)
         } else {
             Value::Text(value.clone())
@@ -107,22 +136,9 @@ impl_from_t_to_value! {
         Value::Text(contents)
     },
 
-    /* TODO conditional on chrono
-    DateTime<Utc> => |value: &DateTime<Utc>| {
-        Value::DateTime(value.clone())
-    },
-    DateTime<Local> => |value: &DateTime<Local>| {
-        let utc_time = value.with_timezone(&Utc);
-        Value::DateTime(utc_time)
-    },
-    DateTime<FixedOffset> => |value: &DateTime<FixedOffset>| {
-        let utc_time = value.with_timezone(&Utc);
-        Value::DateTime(utc_time)
-    },
-
-    */
-
-    bool => |value: &bool| { Value::Boolean(value.clone()) }
+    DateTime => |value: &DateTime| { Value::DateTime(value.clone()) },
+    Duration => |value: &Duration| { Value::Duration(value.clone()) },
+    bool => |value: &bool| { Value::Boolean(*value) }
 
     // TODO ensure coverage
 }
@@ -132,13 +148,57 @@ impl_from_value_to_t_option! {
     i64 => Value::Number(Numeric::Integer(x)) => x,
     f32 => Value::Number(Numeric::Real(r)) => r as f32,
     f64 => Value::Number(Numeric::Real(r)) => r,
+    BigDecimal => Value::Number(Numeric::Decimal(d)) => d,
     String => Value::Text(text) => text,
-    /* conditional on chrono
-    DateTime<Utc> => Value::DateTime(t) => t,
-    DateTime<Local> => Value::DateTime(t) => t.with_timezone(&Local),
-    */
+    DateTime => Value::DateTime(t) => t,
+    Duration => Value::Duration(d) => d,
     bool => Value::Boolean(b) => b
 
-    // TODO &str and DateTime<FixedOffset>
+    // TODO &str
     // TODO ensure coverage
 }
+
+// Integer conversions succeed for any integral numeric sub-type, as long as
+// the value fits into the target type.
+impl_try_from_value_to_t_option! {
+    u8 => Value::Number(n) => n.to_u64().and_then(|x| u8::try_from(x).ok()),
+    u16 => Value::Number(n) => n.to_u64().and_then(|x| u16::try_from(x).ok()),
+    u32 => Value::Number(n) => n.to_u64().and_then(|x| u32::try_from(x).ok()),
+    u64 => Value::Number(n) => n.to_u64(),
+    usize => Value::Number(n) => n.to_u64().and_then(|x| usize::try_from(x).ok()),
+    i128 => Value::Number(n) => n.to_i128(),
+    u128 => Value::Number(n) => n.to_u128(),
+    BigInt => Value::Number(n) => n.to_bigint()
+}
+
+#[cfg(feature = "chrono_dates")]
+impl_from_t_to_value! {
+    chrono::NaiveDate => |value: &chrono::NaiveDate| {
+        Value::DateTime(DateTime::Date(Date::from(*value)))
+    },
+    chrono::NaiveTime => |value: &chrono::NaiveTime| {
+        Value::DateTime(DateTime::Time(Time::from(*value)))
+    },
+    chrono::NaiveDateTime => |value: &chrono::NaiveDateTime| {
+        Value::DateTime(DateTime::from(*value))
+    },
+    chrono::DateTime<chrono::Utc> => |value: &chrono::DateTime<chrono::Utc>| {
+        Value::DateTime(DateTime::from(*value))
+    },
+    chrono::DateTime<chrono::FixedOffset> => |value: &chrono::DateTime<chrono::FixedOffset>| {
+        Value::DateTime(DateTime::from(*value))
+    },
+    chrono::DateTime<chrono::Local> => |value: &chrono::DateTime<chrono::Local>| {
+        Value::DateTime(DateTime::from(*value))
+    }
+}
+
+#[cfg(feature = "chrono_dates")]
+impl_try_from_value_to_t_option! {
+    chrono::NaiveDate => Value::DateTime(DateTime::Date(d)) => chrono::NaiveDate::try_from(&d).ok(),
+    chrono::NaiveTime => Value::DateTime(DateTime::Time(t)) => chrono::NaiveTime::try_from(&t).ok(),
+    chrono::NaiveDateTime => Value::DateTime(t) => chrono::NaiveDateTime::try_from(&t).ok(),
+    chrono::DateTime<chrono::Utc> => Value::DateTime(t) => chrono::DateTime::try_from(&t).ok(),
+    chrono::DateTime<chrono::FixedOffset> => Value::DateTime(t) => chrono::DateTime::try_from(&t).ok(),
+    chrono::DateTime<chrono::Local> => Value::DateTime(t) => chrono::DateTime::try_from(&t).ok()
+}
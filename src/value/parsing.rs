@@ -8,151 +8,1235 @@
 //! CSV, Python, TOML, etc. Same applies for number literals.
 //!
 //! See [this](https://docs.serde.rs/serde_json/index.html).
+//!
+//! # Complex Numbers
+//!
+//! JSON has no syntax for complex numbers, so complex literals are handled
+//! separately. Accepted forms are `a+bi`, `a-bj`, `bi` and `(a,b)`.
+//!
+//! # Locale Number Formats
+//!
+//! Number literals like `1.234,56`, `1 234`, `12%` or `$4.99` are read
+//! according to `ParserOptions`. With the default options, numbers follow
+//! JSON syntax.
 
 use crate::errors::*;
+use crate::value::coercion::*;
 use crate::value::definitions::*;
 use crate::value::primitives::*;
 use crate::value::traits::*;
+use std::str::FromStr;
 
 mod translate_iso8601 {
+    use crate::errors::*;
     use crate::value::definitions::*;
     use crate::value::primitives::*;
     use std::convert::TryInto;
 
-    pub(crate) fn date_to_dk_date(iso8601_date: &iso8601::Date) -> Date {
-        match iso8601_date {
-            iso8601::Date::YMD { year, month, day } => Date::YearMonthDay {
-                year: *year,
-                month: (*month).try_into().unwrap(),
-                day: (*day).try_into().unwrap(),
-            },
-            iso8601::Date::Week { year, ww, d } => Date::YearWeekDay {
-                year: *year,
-                week_in_year: (*ww).try_into().unwrap(),
-                day_in_week: (*d).try_into().unwrap(),
-            },
-            iso8601::Date::Ordinal { year, ddd } => Date::YearDay {
-                year: *year,
-                day_in_year: (*ddd).try_into().unwrap(),
-            },
+    fn conversion_failed(source: &str) -> ParsingError {
+        ParsingError::cannot_parse(source)
+    }
+
+    fn out_of_range(source: &str, component: &str) -> ParsingError {
+        ParsingError::cannot_parse(source)
+            .with_cause(ParsingCause::OutOfRange(component.to_string()))
+    }
+
+    /// Names the first invalid component of a date.
+    fn invalid_date_component(date: &Date) -> &'static str {
+        match date {
+            Date::YearMonthDay { month, .. } if !(1..=12).contains(month) => "month",
+            Date::YearMonthDay { .. } => "day",
+            Date::YearWeekDay { day_in_week, .. } if !(1..=7).contains(day_in_week) => {
+                "day of the week"
+            }
+            Date::YearWeekDay { .. } => "week",
+            Date::YearDay { .. } => "day of the year",
         }
     }
 
-    pub(crate) fn time_to_dk_time(iso8601_time: &iso8601::Time) -> Time {
-        match iso8601_time {
-            iso8601::Time {
-                hour,
-                minute,
-                second,
-                millisecond,
-                tz_offset_hours,
-                tz_offset_minutes,
-            } => {
-                let tz = if *tz_offset_hours == 0 && *tz_offset_minutes == 0 {
-                    TimeZone::Utc
-                } else {
-                    TimeZone::Offset {
-                        hours: (*tz_offset_hours).try_into().unwrap(),
-                        minutes: (*tz_offset_minutes).try_into().unwrap(),
+    /// Names the first invalid component of a time.
+    fn invalid_time_component(time: &Time) -> &'static str {
+        if time.hour > 23 {
+            "hour"
+        } else if time.minute > 59 {
+            "minute"
+        } else if time.second == 60 {
+            "leap second"
+        } else {
+            "second"
+        }
+    }
+
+    /// Converts an `iso8601::Date` and checks it against the calendar, e.g.
+    /// `2021-02-29` or `2020-W54-1` are rejected.
+    pub(crate) fn date_to_dk_date(
+        iso8601_date: &iso8601::Date,
+        source: &str,
+    ) -> Result<Date, ParsingError> {
+        let convert = || -> Option<Date> {
+            Some(match iso8601_date {
+                iso8601::Date::YMD { year, month, day } => Date::YearMonthDay {
+                    year: *year,
+                    month: (*month).try_into().ok()?,
+                    day: (*day).try_into().ok()?,
+                },
+                iso8601::Date::Week { year, ww, d } => Date::YearWeekDay {
+                    year: *year,
+                    week_in_year: (*ww).try_into().ok()?,
+                    day_in_week: (*d).try_into().ok()?,
+                },
+                iso8601::Date::Ordinal { year, ddd } => Date::YearDay {
+                    year: *year,
+                    day_in_year: (*ddd).try_into().ok()?,
+                },
+            })
+        };
+
+        match convert() {
+            Some(date) if date.is_valid() => Ok(date),
+            Some(date) => Err(out_of_range(source, invalid_date_component(&date))),
+            None => Err(out_of_range(source, "date")),
+        }
+    }
+
+    /// Reads the fractional seconds from the literal as nanoseconds, since
+    /// the `iso8601` crate only keeps milliseconds. Digits beyond
+    /// nanoseconds are truncated.
+    ///
+    /// Only called on literals accepted by the `iso8601` crate, in which the
+    /// first `.` or `,` is the decimal separator of the seconds.
+    fn fraction_to_nanos(source: &str) -> u32 {
+        let fraction = match source.find(['.', ',']) {
+            Some(i) => &source[i + 1..],
+            None => return 0,
+        };
+        fraction
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .chain(std::iter::repeat('0'))
+            .take(9)
+            .fold(0, |nanos, digit| {
+                nanos * 10 + digit.to_digit(10).unwrap_or(0)
+            })
+    }
+
+    /// Converts an `iso8601::Time`, keeping the fractional seconds of
+    /// `source` to nanosecond precision.
+    ///
+    /// Times without an offset have an unspecified time zone.
+    ///
+    /// Returns the time and whether it was written as `24:00:00`, i.e. the
+    /// end of the day, which is converted to midnight. Leap seconds are only
+    /// accepted at 23:59:60 UTC.
+    pub(crate) fn time_to_dk_time(
+        iso8601_time: &iso8601::Time,
+        source: &str,
+    ) -> Result<(Time, bool), ParsingError> {
+        let iso8601::Time {
+            hour,
+            minute,
+            second,
+            millisecond: _,
+            tz_offset_hours,
+            tz_offset_minutes,
+        } = iso8601_time;
+        let nanos = fraction_to_nanos(source);
+
+        let offset_seconds = *tz_offset_hours as i64 * 3600 + *tz_offset_minutes as i64 * 60;
+        let timezone = if has_explicit_offset(source) {
+            TimeZone::from_offset_seconds(offset_seconds)
+                .ok_or_else(|| out_of_range(source, "offset"))?
+        } else {
+            TimeZone::Unspecified
+        };
+
+        let end_of_day = *hour == 24;
+        if end_of_day && (*minute != 0 || *second != 0 || nanos != 0) {
+            return Err(out_of_range(source, "hour"));
+        }
+
+        let hour = if end_of_day {
+            0
+        } else {
+            (*hour)
+                .try_into()
+                .map_err(|_| out_of_range(source, "hour"))?
+        };
+        let time = Time {
+            hour,
+            minute: (*minute)
+                .try_into()
+                .map_err(|_| out_of_range(source, "minute"))?,
+            second: (*second)
+                .try_into()
+                .map_err(|_| out_of_range(source, "second"))?,
+            milli: (nanos / 1_000_000) as MilliNumber,
+            micro: (nanos / 1_000 % 1_000) as MicroNumber,
+            nano: (nanos % 1_000) as NanoNumber,
+            timezone,
+        };
+
+        if time.is_valid() {
+            Ok((time, end_of_day))
+        } else {
+            Err(out_of_range(source, invalid_time_component(&time)))
+        }
+    }
+
+    pub(crate) fn datetime_to_dk_datetime(
+        iso8601_struct: &iso8601::DateTime,
+        source: &str,
+    ) -> Result<DateTime, ParsingError> {
+        let date = date_to_dk_date(&iso8601_struct.date, source)?;
+        let (time, end_of_day) = time_to_dk_time(&iso8601_struct.time, source)?;
+        let date = if end_of_day {
+            date.to_epoch_days()
+                .and_then(|days| Date::from_epoch_days(days + 1))
+                .and_then(|next| next.to_representation_of(&date))
+                .ok_or_else(|| out_of_range(source, "date"))?
+        } else {
+            date
+        };
+        Ok(DateTime::Full { date, time })
+    }
+
+    /// Splits an RFC 9557 zone name suffix like `[Europe/Paris]` off the
+    /// literal.
+    fn split_zone_name(s: &str) -> (&str, Option<&str>) {
+        match (s.find('['), s.strip_suffix(']')) {
+            (Some(i), Some(rest)) => (&s[..i], Some(&rest[i + 1..])),
+            _ => (s, None),
+        }
+    }
+
+    fn has_explicit_offset(literal: &str) -> bool {
+        let time_part = match literal.find('T') {
+            Some(i) => &literal[i + 1..],
+            None => literal,
+        };
+        time_part.contains(['Z', '+', '-'])
+    }
+
+    /// Places a parsed time or date time into the named zone. An explicit
+    /// offset in the literal has to match the offset of the zone at that
+    /// time.
+    fn apply_zone_name(
+        datetime: DateTime,
+        name: &str,
+        literal: &str,
+        source: &str,
+    ) -> Result<DateTime, ParsingError> {
+        let named = TimeZone::named(name).map_err(|_| {
+            conversion_failed(source)
+                .at_offset(literal.len() + 1)
+                .with_expected("a known time zone name")
+        })?;
+        let (date, mut time) = match datetime {
+            DateTime::Full { date, time } => (Some(date), time),
+            DateTime::Time(time) => (None, time),
+            DateTime::Date(_) => {
+                return Err(conversion_failed(source)
+                    .at_offset(literal.len())
+                    .with_expected("a time before the time zone name"))
+            }
+        };
+        let offset_mismatch =
+            || conversion_failed(source).with_expected(&format!("an offset of the zone {}", name));
+        let offset = time.timezone.fixed_offset_seconds();
+        time.timezone = named;
+
+        let result = match date {
+            Some(date) => DateTime::Full { date, time },
+            None if has_explicit_offset(literal) => return Err(offset_mismatch()),
+            None => return Ok(DateTime::Time(time)),
+        };
+        match result.offset_seconds() {
+            Some(resolved) if !has_explicit_offset(literal) || offset == Some(resolved) => {
+                Ok(result)
+            }
+            Some(_) => Err(offset_mismatch()),
+            // The wall clock time was skipped when the clocks were turned
+            // forward.
+            None => Err(out_of_range(source, "local time")),
+        }
+    }
+
+    /// A position in a literal. The `iso8601` crate reads the longest prefix
+    /// it can, e.g. `2021-W05` as the time 20:21, so literals are checked to
+    /// be complete before they are passed on.
+    struct Cursor<'a> {
+        rest: &'a str,
+    }
+
+    impl<'a> Cursor<'a> {
+        /// Skips `c` if it is next.
+        fn skip(&mut self, c: char) -> bool {
+            match self.rest.strip_prefix(c) {
+                Some(rest) => {
+                    self.rest = rest;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        /// Reads exactly `n` digits.
+        fn digits(&mut self, n: usize) -> Option<u32> {
+            let digits = self.rest.get(..n)?;
+            if !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            self.rest = &self.rest[n..];
+            digits.parse().ok()
+        }
+
+        /// Skips all digits that are next, and returns how many there were.
+        fn skip_digits(&mut self) -> usize {
+            let count = self.rest.bytes().take_while(u8::is_ascii_digit).count();
+            self.rest = &self.rest[count..];
+            count
+        }
+
+        /// Runs `read`, and goes back to the current position if it fails.
+        fn attempt<T>(&mut self, read: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+            let start = self.rest;
+            let result = read(self);
+            if result.is_none() {
+                self.rest = start;
+            }
+            result
+        }
+    }
+
+    /// Reads a whole date literal, i.e. `YYYY-MM-DD`, `YYYY-Www-D` or
+    /// `YYYY-DDD`, or the same without separators, optionally with a sign
+    /// before the year.
+    ///
+    /// Components are not checked against the calendar, see
+    /// `date_to_dk_date`.
+    fn read_date(s: &str) -> Option<iso8601::Date> {
+        let mut cursor = Cursor { rest: s };
+        let sign = if cursor.skip('-') {
+            -1
+        } else {
+            cursor.skip('+');
+            1
+        };
+        let year = sign * cursor.digits(4)? as i32;
+        let extended = cursor.skip('-');
+        let separator = |cursor: &mut Cursor| !extended || cursor.skip('-');
+
+        let date = if cursor.skip('W') {
+            let ww = cursor.digits(2)?;
+            if !separator(&mut cursor) {
+                return None;
+            }
+            iso8601::Date::Week {
+                year,
+                ww,
+                d: cursor.digits(1)?,
+            }
+        } else if cursor.rest.len() == 3 {
+            iso8601::Date::Ordinal {
+                year,
+                ddd: cursor.digits(3)?,
+            }
+        } else {
+            let month = cursor.digits(2)?;
+            if !separator(&mut cursor) {
+                return None;
+            }
+            iso8601::Date::YMD {
+                year,
+                month,
+                day: cursor.digits(2)?,
+            }
+        };
+        if cursor.rest.is_empty() {
+            Some(date)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the whole literal is a time, i.e.
+    /// `hh:mm[:ss][.f][Z|±hh[:mm]]` or the same without colons.
+    fn is_time(s: &str) -> bool {
+        let mut cursor = Cursor { rest: s };
+        let mut read = || -> Option<()> {
+            cursor.digits(2)?;
+            cursor.skip(':');
+            cursor.digits(2)?;
+            cursor.attempt(|cursor| {
+                cursor.skip(':');
+                cursor.digits(2)
+            });
+            if (cursor.skip('.') || cursor.skip(',')) && cursor.skip_digits() == 0 {
+                return None;
+            }
+            if !cursor.skip('Z') {
+                cursor.attempt(|cursor| {
+                    if !cursor.skip('+') && !cursor.skip('-') {
+                        return None;
                     }
-                };
+                    cursor.digits(2)?;
+                    cursor.attempt(|cursor| {
+                        cursor.skip(':');
+                        cursor.digits(2)
+                    });
+                    Some(())
+                });
+            }
+            Some(())
+        };
+        read().is_some() && cursor.rest.is_empty()
+    }
+
+    fn read_time(s: &str, source: &str) -> Result<iso8601::Time, ParsingError> {
+        iso8601::time(s).map_err(|error| {
+            conversion_failed(source)
+                .with_expected("a time")
+                .with_cause(ParsingCause::Iso8601(error))
+        })
+    }
 
-                Time {
-                    hour: (*hour).try_into().unwrap(),
-                    minute: (*minute).try_into().unwrap(),
-                    second: (*second).try_into().unwrap(),
-                    milli: (*millisecond).try_into().unwrap(),
-                    micro: 0,
-                    nano: 0,
-                    timezone: tz,
+    /// Reads dates, times and date times. Week dates are read here, since the
+    /// `iso8601` crate only accepts 52 weeks.
+    ///
+    /// As in ISO 8601, a time on its own needs the `T` designator if it is
+    /// written without colons, e.g. `T1230`, so that `1230` is not a time.
+    pub(crate) fn iso8601_to_dk_value(s: &str) -> Result<Value, ParsingError> {
+        let (literal, zone_name) = split_zone_name(s);
+        let unreadable = || {
+            conversion_failed(s)
+                .with_expected("a date, time or date time")
+                .with_cause(ParsingCause::Iso8601(literal.to_string()))
+        };
+        let time_only = |time: &str| -> Result<DateTime, ParsingError> {
+            let (time, _) = time_to_dk_time(&read_time(time, s)?, literal)?;
+            Ok(DateTime::Time(time))
+        };
+        let datetime = match literal.find('T') {
+            Some(0) if is_time(&literal[1..]) => time_only(&literal[1..])?,
+            Some(i) => {
+                let (date, time) = (&literal[..i], &literal[i + 1..]);
+                match read_date(date) {
+                    Some(date) if is_time(time) => {
+                        let iso8601_struct = iso8601::DateTime {
+                            date,
+                            time: read_time(time, s)?,
+                        };
+                        datetime_to_dk_datetime(&iso8601_struct, literal)?
+                    }
+                    _ => return Err(unreadable()),
+                }
+            }
+            None => {
+                if let Some(iso8601_date) = read_date(literal) {
+                    DateTime::Date(date_to_dk_date(&iso8601_date, literal)?)
+                } else if is_time(literal) && literal[2..].starts_with(':') {
+                    time_only(literal)?
+                } else {
+                    return Err(unreadable());
                 }
             }
+        };
+
+        match zone_name {
+            Some(name) => Ok(Value::DateTime(apply_zone_name(
+                datetime, name, literal, s,
+            )?)),
+            None => Ok(Value::DateTime(datetime)),
         }
     }
+}
+
+pub(crate) use translate_iso8601::iso8601_to_dk_value;
+
+mod complex_literals {
+    use crate::value::primitives::*;
 
-    pub(crate) fn datetime_to_dk_datetime(iso8601_struct: &iso8601::DateTime) -> DateTime {
-        let date = date_to_dk_date(&iso8601_struct.date);
-        let time = time_to_dk_time(&iso8601_struct.time);
-        DateTime::Full { date, time }
+    fn is_float_syntax(s: &str) -> bool {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
     }
 
-    pub(crate) fn iso8601_to_dk_value(s: &str) -> Result<Value, ()> {
-        if let Ok(iso8601_struct) = iso8601::datetime(s) {
-            let datetime = datetime_to_dk_datetime(&iso8601_struct);
-            Ok(Value::DateTime(datetime))
-        } else if let Ok(iso8601_date) = iso8601::date(s) {
-            let date = date_to_dk_date(&iso8601_date);
-            Ok(Value::DateTime(DateTime::Date(date)))
-        } else if let Ok(iso8601_time) = iso8601::time(s) {
-            let time = time_to_dk_time(&iso8601_time);
-            Ok(Value::DateTime(DateTime::Time(time)))
+    fn parse_component(s: &str) -> Option<f64> {
+        let s = s.trim();
+        if is_float_syntax(s) {
+            s.parse::<f64>().ok()
         } else {
-            Err(())
+            None
+        }
+    }
+
+    /// Parses the imaginary coefficient, where a lone sign means 1 or -1.
+    fn parse_imaginary(s: &str) -> Option<f64> {
+        match s {
+            "" | "+" => Some(1.0),
+            "-" => Some(-1.0),
+            _ => parse_component(s),
+        }
+    }
+
+    /// Parses `(a,b)` tuples and `a+bi`/`a+bj` literals.
+    pub(crate) fn parse_complex(s: &str) -> Option<Numeric> {
+        let s = s.trim();
+
+        if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+            let mut parts = inner.split(',');
+            return match (parts.next(), parts.next(), parts.next()) {
+                (Some(re), Some(im), None) => {
+                    Some(Numeric::Complex(parse_component(re)?, parse_component(im)?))
+                }
+                _ => None,
+            };
+        }
+
+        let body = s.strip_suffix('i').or_else(|| s.strip_suffix('j'))?;
+        if !is_float_syntax(body) || !body.chars().any(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        // The real and imaginary parts are separated by the last sign that is
+        // not the sign of an exponent or the leading sign.
+        let bytes = body.as_bytes();
+        let split = (1..bytes.len())
+            .rev()
+            .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'));
+
+        match split {
+            Some(i) => Some(Numeric::Complex(
+                parse_component(&body[..i])?,
+                parse_imaginary(&body[i..])?,
+            )),
+            None => Some(Numeric::Complex(0.0, parse_imaginary(body)?)),
         }
     }
 }
 
-fn jsvalue_to_dkvalue(jsvalue: &serde_json::Value) -> Value {
-    match jsvalue {
-        serde_json::Value::Null => Value::Missing(Empty::Expected),
-        serde_json::Value::Bool(x) => Value::Boolean(*x),
-        serde_json::Value::String(s) => {
-            if let Ok(datetime) = translate_iso8601::iso8601_to_dk_value(s) {
-                datetime
+mod locale_numbers {
+    use super::{ParserOptions, PercentHandling};
+    use crate::errors::ParsingError;
+    use crate::value::primitives::*;
+    use std::str::FromStr;
+
+    // Thousands separators that look like a space.
+    const SPACES: [char; 3] = [' ', '\u{a0}', '\u{202f}'];
+
+    /// A number literal with the sign, currency symbol and percent sign
+    /// separated from the digits.
+    pub(crate) struct NumberLiteral<'a> {
+        negative: bool,
+        percent: bool,
+        body: &'a str,
+    }
+
+    fn strip_currency<'a>(s: &'a str, options: &ParserOptions) -> &'a str {
+        for symbol in options.currency_symbols.iter() {
+            if let Some(rest) = s.strip_prefix(symbol.as_str()) {
+                return rest.trim_start();
+            }
+            if let Some(rest) = s.strip_suffix(symbol.as_str()) {
+                return rest.trim_end();
+            }
+        }
+        s
+    }
+
+    fn is_thousands_separator(c: char, options: &ParserOptions) -> bool {
+        match options.thousands_separator {
+            Some(' ') => SPACES.contains(&c),
+            Some(separator) => c == separator,
+            None => false,
+        }
+    }
+
+    impl<'a> NumberLiteral<'a> {
+        /// Splits `s` if it looks like a number, i.e. consists of digits,
+        /// separators and exponents. It may still not be a valid number.
+        pub(crate) fn split(s: &'a str, options: &ParserOptions) -> Option<Self> {
+            let mut negative = false;
+            let mut rest = s.trim();
+            if let Some(unsigned) = rest.strip_prefix('-') {
+                negative = true;
+                rest = unsigned.trim_start();
+            }
+            rest = strip_currency(rest, options);
+            if !negative {
+                if let Some(unsigned) = rest.strip_prefix('-') {
+                    negative = true;
+                    rest = unsigned;
+                }
+            }
+            let mut percent = false;
+            if options.percent != PercentHandling::Reject {
+                if let Some(number) = rest.strip_suffix('%') {
+                    percent = true;
+                    rest = number.trim_end();
+                }
+            }
+
+            let starts_with_digit = rest
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_digit() || c == options.decimal_separator);
+            let is_number_like = rest.chars().all(|c| {
+                c.is_ascii_digit()
+                    || matches!(c, '.' | ',' | 'e' | 'E' | '+' | '-')
+                    || c == options.decimal_separator
+                    || is_thousands_separator(c, options)
+            });
+
+            if starts_with_digit && is_number_like {
+                Some(Self {
+                    negative,
+                    percent,
+                    body: rest,
+                })
             } else {
-                Value::Text(s.clone())
+                None
             }
         }
-        serde_json::Value::Number(jsnum) => {
-            if jsnum.is_i64() {
-                if let Some(result) = jsnum.as_i64() {
-                    Value::Number(Numeric::Integer(result))
-                } else {
-                    Value::Missing(Empty::Unexpected)
+
+        /// Rewrites the literal in JSON syntax, checking the grouping of
+        /// thousands. `source` is the literal that was split.
+        pub(crate) fn to_json(
+            &self,
+            source: &str,
+            options: &ParserOptions,
+        ) -> Result<String, ParsingError> {
+            let offset_of = |part: &str| part.as_ptr() as usize - source.as_ptr() as usize;
+            let fail_at = |offset, expected| {
+                ParsingError::cannot_parse(source)
+                    .at_offset(offset)
+                    .with_expected(expected)
+            };
+
+            let (mantissa, exponent) = match self.body.find(['e', 'E']) {
+                Some(i) => (&self.body[..i], Some(&self.body[i + 1..])),
+                None => (self.body, None),
+            };
+            let mut parts = mantissa.splitn(2, options.decimal_separator);
+            let integer = parts.next().unwrap_or("");
+            let fraction = parts.next();
+
+            let groups: Vec<&str> = if options.thousands_separator.is_some() {
+                integer
+                    .split(|c| is_thousands_separator(c, options))
+                    .collect()
+            } else {
+                vec![integer]
+            };
+            for part in groups.iter().chain(fraction.iter()) {
+                if let Some(i) = part.find(|c: char| !c.is_ascii_digit()) {
+                    return Err(fail_at(offset_of(part) + i, "a digit"));
+                }
+            }
+            if groups.len() > 1 {
+                let misgrouped = std::iter::once(groups[0])
+                    .filter(|group| !(1..=3).contains(&group.len()))
+                    .chain(groups[1..].iter().copied().filter(|group| group.len() != 3))
+                    .next();
+                if let Some(group) = misgrouped {
+                    return Err(fail_at(offset_of(group), "groups of three digits"));
                 }
-            } else if jsnum.is_f64() {
-                if let Some(result) = jsnum.as_f64() {
-                    Value::Number(Numeric::Real(result))
+            }
+
+            let mut json = String::new();
+            if self.negative {
+                json.push('-');
+            }
+            json.push_str(&groups.concat());
+            if let Some(fraction) = fraction {
+                json.push('.');
+                json.push_str(fraction);
+            }
+            if let Some(exponent) = exponent {
+                json.push('e');
+                json.push_str(exponent);
+            }
+
+            if self.percent && options.percent == PercentHandling::Fraction {
+                let fraction = BigDecimal::from_str(&json)
+                    .map_err(|_| ParsingError::cannot_parse(source).with_expected("a number"))?
+                    / BigDecimal::from(100);
+                if fraction.is_integer() {
+                    Ok(fraction.with_scale(0).to_string())
                 } else {
-                    Value::Missing(Empty::Unexpected)
+                    Ok(fraction.normalized().to_string())
                 }
             } else {
-                Value::Missing(Empty::Unexpected) // TODO probably a conversion/parsing error
+                Ok(json)
             }
         }
-        serde_json::Value::Array(arr) => {
-            let mut result: Vec<Value> = Vec::new();
-            for jsvalue_in_arr in arr.iter() {
-                let dkvalue = jsvalue_to_dkvalue(&jsvalue_in_arr);
-                result.push(dkvalue);
-            }
-            Value::Composite(Collection::Array(result))
+    }
+}
+
+/// How number literals with a trailing percent sign are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PercentHandling {
+    /// Literals with a percent sign are not numbers.
+    Reject,
+    /// The percent sign is dropped, so `12%` is read as `12`.
+    Strip,
+    /// The number is divided by 100, so `12%` is read as `0.12`.
+    Fraction,
+}
+
+/// Which strings are read as date times.
+///
+/// Durations are never detected, since strings like `P3D` are as likely to
+/// be codes. Use `Parser::parse_as` with `ValueType::Duration` to read them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateTimeDetection {
+    /// Strings in ISO 8601 format are date times. Only whole strings are
+    /// read, so e.g. `2021W05` or `1234` stay text.
+    Always,
+    /// Strings are always text.
+    Never,
+    /// Strings read by one of the formats are date times, and strings that
+    /// formats read as different date times are a `ParsingError::AmbiguousDate`,
+    /// see `DateParser`. Other strings are text.
+    Formats(Vec<DateTimeFormat>),
+}
+
+/// Options for reading literals that are not in JSON syntax, e.g. numbers
+/// formatted for a locale and tokens for booleans and missing values.
+///
+/// Tokens are compared with the whole literal, without surrounding
+/// whitespace, and are case sensitive.
+///
+/// With bare literals, e.g. for the cells of a CSV file, text needs no
+/// quotes. Numbers, booleans and ISO 8601 date times are detected, and
+/// everything else is read as text.
+///
+/// Use `with_datetime_detection` to keep strings that look like dates, e.g.
+/// product codes like `2020-123`, as text. `DsvReader::with_column_options`
+/// can enable detection only for some columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserOptions {
+    bare_literals: bool,
+    datetime_detection: DateTimeDetection,
+    decimal_separator: char,
+    thousands_separator: Option<char>,
+    percent: PercentHandling,
+    currency_symbols: Vec<String>,
+    true_tokens: Vec<String>,
+    false_tokens: Vec<String>,
+    null_tokens: Vec<String>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_strings(tokens: &[&str]) -> Vec<String> {
+    tokens.iter().map(|token| token.to_string()).collect()
+}
+
+impl ParserOptions {
+    /// Options for JSON syntax.
+    pub fn new() -> Self {
+        Self {
+            bare_literals: false,
+            datetime_detection: DateTimeDetection::Always,
+            decimal_separator: '.',
+            thousands_separator: None,
+            percent: PercentHandling::Reject,
+            currency_symbols: Vec::new(),
+            true_tokens: Vec::new(),
+            false_tokens: Vec::new(),
+            null_tokens: Vec::new(),
         }
-        serde_json::Value::Object(obj) => {
-            let mut result: Vec<(String, Value)> = Vec::new();
-            for (key, jsvalue_in_obj) in obj.iter() {
-                let dkvalue = jsvalue_to_dkvalue(&jsvalue_in_obj);
-                result.push((key.clone(), dkvalue));
-            }
-            Value::Composite(Collection::Object(result))
+    }
+
+    /// Options for bare literals, where empty literals, `NA`, `NULL` and `-`
+    /// are missing values.
+    pub fn bare() -> Self {
+        Self::new()
+            .with_bare_literals(true)
+            .with_null_tokens(&["", "NA", "NULL", "-"])
+    }
+
+    pub fn with_bare_literals(mut self, bare_literals: bool) -> Self {
+        self.bare_literals = bare_literals;
+        self
+    }
+
+    pub fn with_datetime_detection(mut self, detection: DateTimeDetection) -> Self {
+        self.datetime_detection = detection;
+        self
+    }
+
+    /// The formats that strings are read with, if any.
+    pub(crate) fn datetime_formats(&self) -> Option<&[DateTimeFormat]> {
+        match &self.datetime_detection {
+            DateTimeDetection::Formats(formats) => Some(formats),
+            _ => None,
+        }
+    }
+
+    pub fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Separator between groups of three digits in the integer part. A space
+    /// also matches non-breaking spaces.
+    pub fn with_thousands_separator(mut self, separator: Option<char>) -> Self {
+        self.thousands_separator = separator;
+        self
+    }
+
+    pub fn with_percent(mut self, percent: PercentHandling) -> Self {
+        self.percent = percent;
+        self
+    }
+
+    /// Currency symbols or codes to ignore before or after a number, e.g.
+    /// `$` or `EUR`.
+    pub fn with_currency_symbols(mut self, symbols: &[&str]) -> Self {
+        self.currency_symbols = to_strings(symbols);
+        self
+    }
+
+    /// Literals read as `true`, in addition to `true`.
+    pub fn with_true_tokens(mut self, tokens: &[&str]) -> Self {
+        self.true_tokens = to_strings(tokens);
+        self
+    }
+
+    /// Literals read as `false`, in addition to `false`.
+    pub fn with_false_tokens(mut self, tokens: &[&str]) -> Self {
+        self.false_tokens = to_strings(tokens);
+        self
+    }
+
+    /// Literals read as missing values, in addition to `null`.
+    pub fn with_null_tokens(mut self, tokens: &[&str]) -> Self {
+        self.null_tokens = to_strings(tokens);
+        self
+    }
+
+    fn token_value(&self, s: &str) -> Option<Value> {
+        let matches = |tokens: &Vec<String>| tokens.iter().any(|token| token == s);
+        if matches(&self.true_tokens) {
+            Some(Value::Boolean(true))
+        } else if matches(&self.false_tokens) {
+            Some(Value::Boolean(false))
+        } else if matches(&self.null_tokens) {
+            Some(Value::Missing(Empty::Expected))
+        } else {
+            None
         }
     }
 }
 
-pub struct Parser {}
+/// Parses values from literals following JSON syntax.
+///
+/// By default, number literals with a fractional part or an exponent are
+/// parsed into `Numeric::Real`. Use `with_exact_decimals` to parse them into
+/// `Numeric::Decimal` instead, without going through `f64`. Numbers inside
+/// arrays and objects are only read exactly with the `exact_decimals`
+/// feature.
+///
+/// Use `with_epoch_unit` to parse number literals as Unix timestamps, e.g.
+/// for a column of epoch milliseconds.
+///
+/// Use `with_options` to read numbers formatted for a locale and custom
+/// tokens for booleans and missing values, and to control which strings are
+/// read as date times. Use `parse_as` when the type of the value is known.
+pub struct Parser {
+    exact_decimals: bool,
+    epoch_unit: Option<EpochUnit>,
+    options: ParserOptions,
+}
 
 impl Parser {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            exact_decimals: false,
+            epoch_unit: None,
+            options: ParserOptions::new(),
+        }
+    }
+
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Parse non-integer number literals into exact `Numeric::Decimal`s.
+    pub fn with_exact_decimals(mut self, exact_decimals: bool) -> Self {
+        self.exact_decimals = exact_decimals;
+        self
+    }
+
+    /// Parse number literals as Unix timestamps in the given unit, into full
+    /// date times in UTC. Only applies to a number on its own, not to numbers
+    /// in arrays or objects.
+    pub fn with_epoch_unit(mut self, epoch_unit: Option<EpochUnit>) -> Self {
+        self.epoch_unit = epoch_unit;
+        self
+    }
+
+    /// Fails if formats read `s` as different date times, see `DateParser`.
+    fn detect_datetime(
+        &self,
+        s: &str,
+        detection: &DateTimeDetection,
+    ) -> Result<Option<Value>, ParsingError> {
+        match detection {
+            DateTimeDetection::Always => Ok(translate_iso8601::iso8601_to_dk_value(s).ok()),
+            DateTimeDetection::Never => Ok(None),
+            DateTimeDetection::Formats(formats) => match DateParser::parse_with(formats, s) {
+                Ok(datetime) => Ok(Some(datetime)),
+                Err(error @ ParsingError::AmbiguousDate { .. }) => Err(error),
+                Err(_) => Ok(None),
+            },
+        }
+    }
+
+    /// Parses `s` as a value of the type `value_type`, e.g. to keep text
+    /// that looks like a date as text. Missing values are accepted for any
+    /// type.
+    ///
+    /// Strings are only read as date times and durations if `value_type` is
+    /// one of these, in which case ISO 8601 is accepted regardless of the
+    /// `DateTimeDetection` option. Other values are converted to the type
+    /// with `Coercion`, e.g. the number literal `42` is the text `"42"`.
+    pub fn parse_as(&self, s: &str, value_type: &ValueType) -> Result<Value, ParsingError> {
+        let cannot_parse = || ParsingError::cannot_parse(s).with_target_type(value_type);
+        let literal_parser = Parser {
+            exact_decimals: self.exact_decimals,
+            epoch_unit: match value_type {
+                ValueType::DateTime => self.epoch_unit,
+                _ => None,
+            },
+            options: self
+                .options
+                .clone()
+                .with_datetime_detection(DateTimeDetection::Never),
+        };
+
+        let value = literal_parser
+            .parse(s)
+            .map_err(|error| error.with_target_type(value_type))?;
+        match (value, value_type) {
+            (value @ Value::Missing(_), _) => Ok(value),
+            (value, value_type) if value.is_of_type(value_type) => Ok(value),
+            (Value::Text(text), ValueType::DateTime) => {
+                let text = text.trim();
+                if let formats @ DateTimeDetection::Formats(_) = &self.options.datetime_detection {
+                    if let Some(datetime) = self.detect_datetime(text, formats)? {
+                        return Ok(datetime);
+                    }
+                }
+                translate_iso8601::iso8601_to_dk_value(text)
+                    .map_err(|error| error.with_target_type(value_type))
+            }
+            (Value::Text(text), ValueType::Duration) => Duration::from_str(text.trim())
+                .map(Value::Duration)
+                .map_err(|error| error.with_target_type(value_type)),
+            // Bare literals are never JSON, so the text is read again.
+            (Value::Text(text), ValueType::Composite) => {
+                let json_parser = Parser::new().with_exact_decimals(self.exact_decimals);
+                match json_parser.parse(&text) {
+                    Ok(composite @ Value::Composite(_)) => Ok(composite),
+                    Ok(_) => Err(cannot_parse().with_expected("a JSON array or object")),
+                    Err(error) => Err(error.with_target_type(value_type)),
+                }
+            }
+            (value, value_type) => Coercion::new()
+                .convert(&value, value_type)
+                .map_err(|_| cannot_parse()),
+        }
+    }
+
+    fn parse_number(&self, s: &str) -> Option<Result<Value, ParsingError>> {
+        let number = locale_numbers::NumberLiteral::split(s, &self.options)?;
+        Some(number.to_json(s, &self.options).and_then(|json| {
+            // Offsets in the JSON literal do not match the source, so only
+            // the message is kept.
+            serde_json::from_str::<serde_json::Value>(&json)
+                .map_err(|error| {
+                    ParsingError::cannot_parse(s)
+                        .with_expected("a number")
+                        .with_cause(ParsingCause::Json(error.to_string()))
+                })
+                .and_then(|jsvalue| match &jsvalue {
+                    serde_json::Value::Number(jsnum) => Ok(self.number_to_dkvalue(jsnum, &json)),
+                    _ => self.jsvalue_to_dkvalue(&jsvalue),
+                })
+        }))
+    }
+
+    /// Bare literals are only rejected if they are ambiguous dates, anything
+    /// that is not a number, a boolean or a date time is text.
+    fn parse_bare(&self, s: &str) -> Result<Value, ParsingError> {
+        let trimmed = s.trim();
+        match trimmed {
+            "true" => return Ok(Value::Boolean(true)),
+            "false" => return Ok(Value::Boolean(false)),
+            _ => {}
+        }
+        if let Some(Ok(number)) = self.parse_number(trimmed) {
+            return Ok(number);
+        }
+        if let Some(complex) = complex_literals::parse_complex(trimmed) {
+            return Ok(Value::Number(complex));
+        }
+        match self.jsvalue_to_dkvalue(&serde_json::Value::String(trimmed.to_string()))? {
+            Value::Text(_) => Ok(Value::Text(s.to_string())),
+            value => Ok(value),
+        }
+    }
+
+    /// Reads integers beyond 64 bits and exact decimals from `literal`, the
+    /// text of the number. Without the `exact_decimals` feature, `serde_json`
+    /// only keeps the text of numbers on their own, and numbers in arrays and
+    /// objects are read through `f64` if they do not fit in 64 bits.
+    fn number_to_dkvalue(&self, jsnum: &serde_json::Number, literal: &str) -> Value {
+        if jsnum.is_i64() {
+            if let Some(result) = jsnum.as_i64() {
+                Value::Number(Numeric::Integer(result))
+            } else {
+                Value::Missing(Empty::Unexpected)
+            }
+        } else if let Some(result) = jsnum.as_u64() {
+            Value::Number(Numeric::Unsigned(result))
+        } else if let Ok(result) = BigInt::from_str(literal) {
+            Value::Number(Numeric::BigInteger(result))
+        } else if self.exact_decimals {
+            match BigDecimal::from_str(literal) {
+                Ok(result) => Value::Number(Numeric::Decimal(result)),
+                Err(_) => Value::Missing(Empty::Unexpected),
+            }
+        } else if jsnum.is_f64() {
+            if let Some(result) = jsnum.as_f64() {
+                Value::Number(Numeric::Real(result))
+            } else {
+                Value::Missing(Empty::Unexpected)
+            }
+        } else {
+            Value::Missing(Empty::Unexpected) // TODO probably a conversion/parsing error
+        }
+    }
+
+    fn jsvalue_to_dkvalue(&self, jsvalue: &serde_json::Value) -> Result<Value, ParsingError> {
+        Ok(match jsvalue {
+            serde_json::Value::Null => Value::Missing(Empty::Expected),
+            serde_json::Value::Bool(x) => Value::Boolean(*x),
+            serde_json::Value::String(s) => self
+                .detect_datetime(s, &self.options.datetime_detection)?
+                .unwrap_or_else(|| Value::Text(s.clone())),
+            serde_json::Value::Number(jsnum) => self.number_to_dkvalue(jsnum, &jsnum.to_string()),
+            serde_json::Value::Array(arr) => {
+                let mut result: Vec<Value> = Vec::new();
+                for jsvalue_in_arr in arr.iter() {
+                    let dkvalue = self.jsvalue_to_dkvalue(jsvalue_in_arr)?;
+                    result.push(dkvalue);
+                }
+                Value::Composite(Collection::Array(result))
+            }
+            serde_json::Value::Object(obj) => {
+                let mut result: Vec<(String, Value)> = Vec::new();
+                for (key, jsvalue_in_obj) in obj.iter() {
+                    let dkvalue = self.jsvalue_to_dkvalue(jsvalue_in_obj)?;
+                    result.push((key.clone(), dkvalue));
+                }
+                Value::Composite(Collection::Object(result))
+            }
+        })
+    }
+}
+
+/// Parses dates and times that are not in ISO 8601 format, trying a list of
+/// `DateTimeFormat` patterns in order.
+///
+/// Patterns like `%d/%m/%Y` and `%m/%d/%Y` can read the same text as
+/// different dates. Instead of picking the first pattern for each value,
+/// `parse_column` decides between them for a whole column, and reports an
+/// ambiguity if the column does not settle it.
+pub struct DateParser {
+    formats: Vec<DateTimeFormat>,
+}
+
+impl DateParser {
+    pub fn new(formats: Vec<DateTimeFormat>) -> Self {
+        Self { formats }
+    }
+
+    pub fn from_patterns(patterns: &[&str]) -> Result<Self, FormattingError> {
+        let formats = patterns
+            .iter()
+            .map(|pattern| DateTimeFormat::new(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(formats))
+    }
+
+    /// Results of all formats for one value.
+    fn candidates(formats: &[DateTimeFormat], s: &str) -> Vec<Option<DateTime>> {
+        formats.iter().map(|f| f.parse(s).ok()).collect()
     }
+
+    /// Picks the result of the first allowed format that reads the value,
+    /// failing if allowed formats read it as different date times.
+    fn resolve(
+        formats: &[DateTimeFormat],
+        s: &str,
+        candidates: &[Option<DateTime>],
+        allowed: &[bool],
+    ) -> Result<Value, ParsingError> {
+        let mut matches =
+            candidates
+                .iter()
+                .zip(allowed)
+                .enumerate()
+                .filter_map(|(i, (candidate, allowed))| match candidate {
+                    Some(datetime) if *allowed => Some((i, datetime)),
+                    _ => None,
+                });
+
+        let (first_index, first) = match matches.next() {
+            Some(m) => m,
+            None => {
+                let patterns: Vec<&str> = formats.iter().map(|f| f.pattern()).collect();
+                return Err(ParsingError::cannot_parse(s)
+                    .with_expected(&format!("a date time like {}", patterns.join(" or "))));
+            }
+        };
+        let conflicting: Vec<usize> = matches
+            .filter(|(_, datetime)| *datetime != first)
+            .map(|(i, _)| i)
+            .collect();
+
+        if conflicting.is_empty() {
+            Ok(Value::DateTime(first.clone()))
+        } else {
+            Err(ParsingError::AmbiguousDate {
+                value: s.to_string(),
+                patterns: std::iter::once(first_index)
+                    .chain(conflicting)
+                    .map(|i| formats[i].pattern().to_string())
+                    .collect(),
+            })
+        }
+    }
+
+    /// Parses a single value with the first format that reads it. Fails if
+    /// several formats read it as different date times.
+    pub fn parse(&self, s: &str) -> Result<Value, ParsingError> {
+        Self::parse_with(&self.formats, s)
+    }
+
+    fn parse_with(formats: &[DateTimeFormat], s: &str) -> Result<Value, ParsingError> {
+        let allowed = vec![true; formats.len()];
+        Self::resolve(formats, s, &Self::candidates(formats, s), &allowed)
+    }
+
+    /// Parses a column of values. Empty values are missing.
+    ///
+    /// Two formats compete if they read some value in the column as
+    /// different date times. A format is ruled out for the whole column if it
+    /// fails to read a value that a competing format reads, e.g. `25/03/2021`
+    /// rules out `%m/%d/%Y` in favour of `%d/%m/%Y`. If values remain that
+    /// competing formats read differently, the column is ambiguous.
+    pub fn parse_column(&self, values: &[&str]) -> Result<Vec<Value>, ParsingError> {
+        self.parse_column_values(values).into_iter().collect()
+    }
+
+    /// Like `parse_column`, but with the result of each value.
+    pub(crate) fn parse_column_values(&self, values: &[&str]) -> Vec<Result<Value, ParsingError>> {
+        let n = self.formats.len();
+        let candidates: Vec<Vec<Option<DateTime>>> = values
+            .iter()
+            .map(|s| Self::candidates(&self.formats, s.trim()))
+            .collect();
+
+        let mut competing = vec![vec![false; n]; n];
+        for results in candidates.iter() {
+            for a in 0..n {
+                for b in 0..n {
+                    if let (Some(x), Some(y)) = (&results[a], &results[b]) {
+                        competing[a][b] |= x != y;
+                    }
+                }
+            }
+        }
+
+        let mut allowed = vec![true; n];
+        for results in candidates.iter() {
+            for a in 0..n {
+                let beaten = (0..n).any(|b| competing[a][b] && results[b].is_some());
+                if results[a].is_none() && beaten {
+                    allowed[a] = false;
+                }
+            }
+        }
+
+        values
+            .iter()
+            .zip(candidates.iter())
+            .map(|(s, results)| {
+                if s.trim().is_empty() {
+                    Ok(Value::Missing(Empty::Expected))
+                } else {
+                    Self::resolve(&self.formats, s, results, &allowed)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Converts the line and column of a `serde_json` error into a byte offset.
+fn json_error(s: &str, error: &serde_json::Error) -> ParsingError {
+    let line_start: usize = s
+        .split_inclusive('\n')
+        .take(error.line().saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let offset = (line_start + error.column().saturating_sub(1)).min(s.len());
+    ParsingError::cannot_parse(s)
+        .at_offset(offset)
+        .with_cause(ParsingCause::Json(error.to_string()))
 }
 
 impl ParsesValues for Parser {
     fn parse(&self, s: &str) -> Result<Value, ParsingError> {
-        if let Ok(jsvalue) = serde_json::from_str::<serde_json::Value>(s) {
-            Ok(jsvalue_to_dkvalue(&jsvalue))
+        if let Some(value) = self.options.token_value(s.trim()) {
+            return Ok(value);
+        }
+
+        let value = if self.options.bare_literals {
+            self.parse_bare(s)?
+        } else if let Some(number) = self.parse_number(s) {
+            number?
         } else {
-            Err(ParsingError::CannotParseValue(s.to_string()))
+            match serde_json::from_str::<serde_json::Value>(s) {
+                Ok(jsvalue) => self.jsvalue_to_dkvalue(&jsvalue)?,
+                Err(error) => match complex_literals::parse_complex(s) {
+                    Some(complex) => return Ok(Value::Number(complex)),
+                    None => return Err(json_error(s, &error)),
+                },
+            }
+        };
+
+        match (value, self.epoch_unit) {
+            (number @ Value::Number(_), Some(unit)) => Coercion::new()
+                .with_epoch_unit(unit)
+                .convert(&number, &ValueType::DateTime)
+                .map_err(|_| {
+                    ParsingError::cannot_parse(s)
+                        .with_target_type(&ValueType::DateTime)
+                        .with_cause(ParsingCause::OutOfRange("timestamp".to_string()))
+                }),
+            (value, _) => Ok(value),
         }
     }
 }
@@ -0,0 +1,198 @@
+//! Paths Into Composite Values
+//!
+//! A `ValuePath` addresses a value inside arrays and objects, either as a
+//! JSON Pointer like `/address/lines/0` or as a dotted path like
+//! `address.lines.0`. Keys that contain dots can only be addressed with JSON
+//! Pointers, in which `~1` stands for `/` and `~0` for `~`.
+
+use crate::errors::PathError;
+use crate::value::definitions::*;
+use crate::value::primitives::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Escapes a key or index for use in a JSON Pointer, e.g. `a/b` as `a~1b`.
+pub(crate) fn pointer_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(segment: &str, path: &str) -> Result<String, PathError> {
+    let mut result = String::with_capacity(segment.len());
+    let mut chars = segment.chars();
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('0') => result.push('~'),
+            Some('1') => result.push('/'),
+            _ => return Err(PathError::InvalidPath(path.to_string())),
+        }
+    }
+    Ok(result)
+}
+
+/// Reads array indices without signs or leading zeros.
+fn index(segment: &str) -> Option<usize> {
+    match segment.as_bytes() {
+        [b'0'] => Some(0),
+        [b'1'..=b'9', rest @ ..] if rest.iter().all(u8::is_ascii_digit) => segment.parse().ok(),
+        _ => None,
+    }
+}
+
+/// The keys and indices leading to a value inside a composite. The empty
+/// path addresses the value itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ValuePath {
+    segments: Vec<String>,
+}
+
+impl ValuePath {
+    pub fn root() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Returns the path one level deeper.
+    pub fn join(&self, segment: &str) -> Self {
+        let mut segments = self.segments.clone();
+        segments.push(segment.to_string());
+        Self { segments }
+    }
+
+    fn parent(&self) -> Option<(ValuePath, &str)> {
+        let (last, parent) = self.segments.split_last()?;
+        Some((
+            ValuePath {
+                segments: parent.to_vec(),
+            },
+            last,
+        ))
+    }
+}
+
+/// Reads JSON Pointers if the text starts with `/`, and dotted paths
+/// otherwise.
+impl FromStr for ValuePath {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments = if s.is_empty() {
+            Vec::new()
+        } else if let Some(pointer) = s.strip_prefix('/') {
+            pointer
+                .split('/')
+                .map(|segment| unescape(segment, s))
+                .collect::<Result<_, _>>()?
+        } else if s.split('.').any(str::is_empty) {
+            return Err(PathError::InvalidPath(s.to_string()));
+        } else {
+            s.split('.').map(String::from).collect()
+        };
+        Ok(Self { segments })
+    }
+}
+
+/// Writes the path as a JSON Pointer.
+impl std::fmt::Display for ValuePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for segment in self.segments.iter() {
+            write!(f, "/{}", pointer_segment(segment))?;
+        }
+        Ok(())
+    }
+}
+
+impl Value {
+    fn child(&self, segment: &str) -> Option<&Value> {
+        match self {
+            Value::Composite(Collection::Array(items)) => items.get(index(segment)?),
+            Value::Composite(Collection::Object(entries)) => entries
+                .iter()
+                .find(|(key, _)| key == segment)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn child_mut(&mut self, segment: &str) -> Option<&mut Value> {
+        match self {
+            Value::Composite(Collection::Array(items)) => items.get_mut(index(segment)?),
+            Value::Composite(Collection::Object(entries)) => entries
+                .iter_mut()
+                .find(|(key, _)| key == segment)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value at a JSON Pointer or dotted path, e.g. `/a/0/b` or
+    /// `a.0.b`.
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        self.get_path(&path.parse().ok()?)
+    }
+
+    pub fn get_mut(&mut self, path: &str) -> Option<&mut Value> {
+        self.get_path_mut(&path.parse().ok()?)
+    }
+
+    /// Replaces the value at `path`, returning the previous one. Keys are
+    /// added to objects, and arrays grow by one if the index is their
+    /// length or `-`.
+    pub fn set(&mut self, path: &str, value: Value) -> Result<Option<Value>, PathError> {
+        self.set_path(&path.parse()?, value)
+    }
+
+    pub fn get_path(&self, path: &ValuePath) -> Option<&Value> {
+        path.segments
+            .iter()
+            .try_fold(self, |value, segment| value.child(segment))
+    }
+
+    pub fn get_path_mut(&mut self, path: &ValuePath) -> Option<&mut Value> {
+        path.segments
+            .iter()
+            .try_fold(self, |value, segment| value.child_mut(segment))
+    }
+
+    pub fn set_path(&mut self, path: &ValuePath, value: Value) -> Result<Option<Value>, PathError> {
+        let (parent_path, last) = match path.parent() {
+            Some(parent) => parent,
+            None => return Ok(Some(std::mem::replace(self, value))),
+        };
+        let not_found = || PathError::NotFound(path.to_string());
+        match self.get_path_mut(&parent_path).ok_or_else(not_found)? {
+            Value::Composite(Collection::Object(entries)) => {
+                match entries.iter_mut().find(|(key, _)| key == last) {
+                    Some((_, old)) => Ok(Some(std::mem::replace(old, value))),
+                    None => {
+                        entries.push((last.to_string(), value));
+                        Ok(None)
+                    }
+                }
+            }
+            Value::Composite(Collection::Array(items)) => {
+                match if last == "-" {
+                    Some(items.len())
+                } else {
+                    index(last)
+                } {
+                    Some(i) if i < items.len() => Ok(Some(std::mem::replace(&mut items[i], value))),
+                    Some(i) if i == items.len() => {
+                        items.push(value);
+                        Ok(None)
+                    }
+                    _ => Err(not_found()),
+                }
+            }
+            _ => Err(not_found()),
+        }
+    }
+}
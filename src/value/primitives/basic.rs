@@ -1,3 +1,6 @@
+pub use bigdecimal::num_bigint::BigInt;
+pub use bigdecimal::BigDecimal;
+use bigdecimal::{FromPrimitive, ToPrimitive};
 use serde::{Deserialize, Serialize};
 
 /// *Primitive*: A type for rich null values.
@@ -12,10 +15,180 @@ pub enum Empty {
 }
 
 /// *Primitive*: Numeric value type.
-#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+///
+/// Equality, ordering and arithmetic are implemented in
+/// `datakit::value::arithmetic`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Numeric {
     Integer(i64),
     Real(f64),
     Complex(f64, f64),
+    /// Exact decimal number with arbitrary precision and scale.
+    Decimal(BigDecimal),
+    /// Unsigned integer, for values above `i64::MAX`, e.g. IDs.
+    Unsigned(u64),
+    /// Integer with arbitrary precision. Serialized as a decimal string.
+    BigInteger(#[serde(with = "bigint_as_string")] BigInt),
+}
+
+/// The sub-type of a `Numeric` value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NumericType {
+    Integer,
+    Real,
+    Complex,
+    Decimal,
+    Unsigned,
+    BigInteger,
+}
+
+impl Numeric {
+    /// Returns the sub-type of the numeric value.
+    pub fn numeric_type(&self) -> NumericType {
+        match self {
+            Numeric::Integer(_) => NumericType::Integer,
+            Numeric::Real(_) => NumericType::Real,
+            Numeric::Complex(_, _) => NumericType::Complex,
+            Numeric::Decimal(_) => NumericType::Decimal,
+            Numeric::Unsigned(_) => NumericType::Unsigned,
+            Numeric::BigInteger(_) => NumericType::BigInteger,
+        }
+    }
+
+    /// Returns true if the numeric value is equal to zero.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Numeric::Integer(i) => *i == 0,
+            Numeric::Real(r) => *r == 0.0,
+            Numeric::Complex(re, im) => *re == 0.0 && *im == 0.0,
+            Numeric::Decimal(d) => bigdecimal::Zero::is_zero(d),
+            Numeric::Unsigned(u) => *u == 0,
+            Numeric::BigInteger(b) => bigdecimal::Zero::is_zero(b),
+        }
+    }
+
+    /// Creates the smallest integer representation of `value`, i.e. an
+    /// `Integer` if it fits into an `i64`, an `Unsigned` if it fits into an
+    /// `u64` and a `BigInteger` otherwise.
+    pub fn from_bigint(value: BigInt) -> Self {
+        if let Some(i) = value.to_i64() {
+            Numeric::Integer(i)
+        } else if let Some(u) = value.to_u64() {
+            Numeric::Unsigned(u)
+        } else {
+            Numeric::BigInteger(value)
+        }
+    }
+
+    /// Returns the value as an arbitrary precision integer, if it represents
+    /// an integer exactly.
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Numeric::Integer(i) => Some(BigInt::from(*i)),
+            Numeric::Unsigned(u) => Some(BigInt::from(*u)),
+            Numeric::BigInteger(b) => Some(b.clone()),
+            Numeric::Real(r) if r.fract() == 0.0 => BigInt::from_f64(*r),
+            Numeric::Decimal(d) if d.is_integer() => {
+                Some(d.with_scale(0).into_bigint_and_exponent().0)
+            }
+            Numeric::Complex(re, im) if *im == 0.0 && re.fract() == 0.0 => BigInt::from_f64(*re),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a decimal, if it can be represented exactly.
+    ///
+    /// Real and complex values are not converted, since their binary
+    /// representation rarely corresponds to the intended decimal value.
+    pub(crate) fn as_decimal(&self) -> Option<BigDecimal> {
+        match self {
+            Numeric::Decimal(d) => Some(d.clone()),
+            Numeric::Integer(_) | Numeric::Unsigned(_) | Numeric::BigInteger(_) => {
+                self.to_bigint().map(BigDecimal::from)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a real number. Complex values are only converted
+    /// if their imaginary part is zero.
+    pub(crate) fn as_real(&self) -> Option<f64> {
+        match self {
+            Numeric::Integer(i) => Some(*i as f64),
+            Numeric::Real(r) => Some(*r),
+            Numeric::Complex(re, im) if *im == 0.0 => Some(*re),
+            Numeric::Complex(_, _) => None,
+            Numeric::Decimal(d) => d.to_f64(),
+            Numeric::Unsigned(u) => Some(*u as f64),
+            Numeric::BigInteger(b) => b.to_f64(),
+        }
+    }
+
+    pub(crate) fn as_complex(&self) -> (f64, f64) {
+        match self {
+            Numeric::Complex(re, im) => (*re, *im),
+            other => (other.as_real().unwrap_or(f64::NAN), 0.0),
+        }
+    }
+}
+
+/// Formats numbers so that they can be parsed back by
+/// `datakit::value::parsing::Parser`. Complex numbers are written as `a+bi`.
+impl std::fmt::Display for Numeric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Numeric::Integer(i) => write!(f, "{}", i),
+            Numeric::Real(r) => write!(f, "{}", r),
+            Numeric::Complex(re, im) => {
+                let sign = if im.is_sign_negative() { '-' } else { '+' };
+                write!(f, "{}{}{}i", re, sign, im.abs())
+            }
+            Numeric::Decimal(d) => write!(f, "{}", d),
+            Numeric::Unsigned(u) => write!(f, "{}", u),
+            Numeric::BigInteger(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+/// Exact conversions to primitive number types.
+///
+/// Integer conversions only succeed if the value is integral and fits into
+/// the target type. Conversions to `f64` are rounded to the nearest value.
+impl ToPrimitive for Numeric {
+    fn to_i64(&self) -> Option<i64> {
+        self.to_bigint().and_then(|b| b.to_i64())
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.to_bigint().and_then(|b| b.to_u64())
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        self.to_bigint().and_then(|b| b.to_i128())
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        self.to_bigint().and_then(|b| b.to_u128())
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        self.as_real()
+    }
+}
+
+mod bigint_as_string {
+    use super::BigInt;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(value: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        BigInt::from_str(&s).map_err(de::Error::custom)
+    }
 }
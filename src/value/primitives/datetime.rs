@@ -1,4 +1,18 @@
+pub(crate) mod calendar;
+#[cfg(feature = "chrono_dates")]
+mod chrono_conversions;
+mod duration;
+mod format;
+pub(crate) mod zones;
+
+pub use duration::*;
+pub use format::*;
+
+use crate::errors::ParsingError;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::str::FromStr;
 
 pub(crate) type YearNumber = i32;
 pub(crate) type MonthNumber = u8;
@@ -16,10 +30,114 @@ pub(crate) type NanoNumber = u16;
 #[serde(rename_all = "camelCase")]
 pub enum TimeZone {
     Utc,
-    Offset { hours: i16, minutes: i16 },
+    Offset {
+        hours: i16,
+        minutes: i16,
+    },
+    /// A zone from the IANA time zone database, e.g. `Europe/Luxembourg`,
+    /// whose offset depends on the date due to daylight saving time. Offsets
+    /// of named zones are only available with the `timezones` feature.
+    Named(String),
+    /// No zone information, as in the ISO 8601 literal `12:00:00`. In
+    /// calculations and comparisons, such times are treated as UTC.
+    Unspecified,
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+impl TimeZone {
+    /// Creates a named time zone, checking that the zone exists in the time
+    /// zone database.
+    pub fn named(name: &str) -> Result<Self, ParsingError> {
+        if zones::is_known(name) {
+            Ok(TimeZone::Named(name.to_string()))
+        } else {
+            Err(ParsingError::cannot_parse(name).with_expected("a known time zone name"))
+        }
+    }
+
+    /// Creates a fixed offset from a number of seconds, truncated to whole
+    /// minutes. A zero offset is `Utc`.
+    pub fn from_offset_seconds(seconds: i64) -> Option<Self> {
+        if seconds / 60 == 0 {
+            return Some(TimeZone::Utc);
+        }
+        Some(TimeZone::Offset {
+            hours: i16::try_from(seconds / 3600).ok()?,
+            minutes: (seconds % 3600 / 60) as i16,
+        })
+    }
+
+    /// Offset from UTC in seconds, if it does not depend on the date, i.e.
+    /// for all but named zones.
+    pub fn fixed_offset_seconds(&self) -> Option<i64> {
+        match self {
+            TimeZone::Utc | TimeZone::Unspecified => Some(0),
+            TimeZone::Offset { hours, minutes } => {
+                Some(*hours as i64 * 3600 + *minutes as i64 * 60)
+            }
+            TimeZone::Named(_) => None,
+        }
+    }
+
+    /// Offset from UTC in seconds at the given instant.
+    pub fn offset_seconds_at(&self, utc_seconds: i64) -> Option<i64> {
+        match self {
+            TimeZone::Named(name) => zones::offset_at_utc(name, utc_seconds),
+            _ => self.fixed_offset_seconds(),
+        }
+    }
+
+    /// Offset from UTC in seconds for a wall clock time in this zone, given
+    /// in seconds since 1970-01-01T00:00:00 local time. See
+    /// `zones::offset_at_local` for how DST transitions are resolved.
+    fn offset_seconds_at_local(&self, local_seconds: i64) -> Option<i64> {
+        match self {
+            TimeZone::Named(name) => zones::offset_at_local(name, local_seconds),
+            _ => self.fixed_offset_seconds(),
+        }
+    }
+}
+
+/// Parses `Z`, `UTC`, offsets like `+01:00`, `-0530` or `+02`, and zone names
+/// like `Europe/Luxembourg`.
+impl FromStr for TimeZone {
+    type Err = ParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "Z" || s.eq_ignore_ascii_case("UTC") {
+            return Ok(TimeZone::Utc);
+        }
+
+        let sign = match s.chars().next() {
+            Some('+') => 1,
+            Some('-') => -1,
+            _ => return TimeZone::named(s),
+        };
+        let error = || ParsingError::cannot_parse(s).with_expected("an offset like +01:00");
+        let digits = s[1..].replace(':', "");
+        if !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+        let (hours, minutes) = match digits.len() {
+            2 => (&digits[..], "0"),
+            4 => (&digits[..2], &digits[2..]),
+            _ => return Err(error()),
+        };
+        let hours: i64 = hours.parse().map_err(|_| error())?;
+        let minutes: i64 = minutes.parse().map_err(|_| error())?;
+        if hours > 23 || minutes > 59 {
+            return Err(error());
+        }
+        TimeZone::from_offset_seconds(sign * (hours * 3600 + minutes * 60)).ok_or_else(error)
+    }
+}
+
+/// A calendar date in one of the ISO 8601 representations.
+///
+/// Valid dates compare by the day they denote, so e.g. `2020-W01-1`,
+/// `2019-365` and `2019-12-30` are equal. Invalid dates (see `is_valid`)
+/// are only equal to structurally identical dates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Date {
     YearDay {
@@ -38,18 +156,151 @@ pub enum Date {
     },
 }
 
+impl Date {
+    /// Returns true if the date exists in the (proleptic) Gregorian calendar.
+    ///
+    /// Checks month lengths including leap years, the number of days in the
+    /// year for ordinal dates, and the number of weeks in the ISO week-year
+    /// for week dates.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Date::YearMonthDay { year, month, day } => {
+                match calendar::days_in_month(*year as i64, *month as i64) {
+                    Some(days) => *day >= 1 && (*day as i64) <= days,
+                    None => false,
+                }
+            }
+            Date::YearDay { year, day_in_year } => {
+                *day_in_year >= 1 && (*day_in_year as i64) <= calendar::days_in_year(*year as i64)
+            }
+            Date::YearWeekDay {
+                year,
+                week_in_year,
+                day_in_week,
+            } => {
+                (1..=7).contains(day_in_week)
+                    && *week_in_year >= 1
+                    && calendar::iso_weeks_in_year(*year as i64)
+                        .is_some_and(|weeks| (*week_in_year as i64) <= weeks)
+            }
+        }
+    }
+
+    /// Returns the number of days since 1970-01-01, if the date is valid.
+    pub fn to_epoch_days(&self) -> Option<i64> {
+        if !self.is_valid() {
+            return None;
+        }
+
+        match self {
+            Date::YearMonthDay { year, month, day } => {
+                calendar::days_from_civil(*year as i64, *month as i64, *day as i64)
+            }
+            Date::YearDay { year, day_in_year } => {
+                calendar::days_from_ordinal(*year as i64, *day_in_year as i64)
+            }
+            Date::YearWeekDay {
+                year,
+                week_in_year,
+                day_in_week,
+            } => calendar::days_from_iso_week(
+                *year as i64,
+                *week_in_year as i64,
+                *day_in_week as i64,
+            ),
+        }
+    }
+
+    /// Creates a `YearMonthDay` date from a number of days since 1970-01-01.
+    ///
+    /// Returns `None` if the year falls outside the range of `YearNumber`.
+    pub fn from_epoch_days(days: i64) -> Option<Self> {
+        let (year, month, day) = calendar::civil_from_days(days)?;
+        Some(Date::YearMonthDay {
+            year: YearNumber::try_from(year).ok()?,
+            month: month as MonthNumber,
+            day: day as DayNumber,
+        })
+    }
+
+    /// Converts the date to the `YearMonthDay` representation.
+    pub fn to_year_month_day(&self) -> Option<Self> {
+        Self::from_epoch_days(self.to_epoch_days()?)
+    }
+
+    /// Converts the date to the `YearDay` (ordinal) representation.
+    pub fn to_year_day(&self) -> Option<Self> {
+        let days = self.to_epoch_days()?;
+        let (year, _, _) = calendar::civil_from_days(days)?;
+        Some(Date::YearDay {
+            year: YearNumber::try_from(year).ok()?,
+            day_in_year: calendar::day_in_year(days)? as DayOrdinal,
+        })
+    }
+
+    /// Converts the date to the `YearWeekDay` representation. Note that the
+    /// ISO week-year may differ from the calendar year around new year.
+    pub fn to_year_week_day(&self) -> Option<Self> {
+        let (year, week, day) = calendar::iso_week_from_days(self.to_epoch_days()?)?;
+        Some(Date::YearWeekDay {
+            year: YearNumber::try_from(year).ok()?,
+            week_in_year: week as WeekNumber,
+            day_in_week: day as DayNumber,
+        })
+    }
+
+    /// Structural key, used to compare invalid dates.
+    fn components(&self) -> (u8, YearNumber, u16, u16) {
+        match self {
+            Date::YearDay { year, day_in_year } => (0, *year, *day_in_year, 0),
+            Date::YearMonthDay { year, month, day } => (1, *year, *month as u16, *day as u16),
+            Date::YearWeekDay {
+                year,
+                week_in_year,
+                day_in_week,
+            } => (2, *year, *week_in_year as u16, *day_in_week as u16),
+        }
+    }
+}
+
+impl PartialEq for Date {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.to_epoch_days(), other.to_epoch_days()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.components() == other.components(),
+        }
+    }
+}
+
+impl PartialOrd for Date {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.to_epoch_days(), other.to_epoch_days()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            (None, None) => self.components().partial_cmp(&other.components()),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for Date {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Date::YearMonthDay { year, month, day } => {
-                write!(f, "{:0>4}-{:0>2}-{:0>2}", year, month, day)
+                let year = format::format_year(*year as i64);
+                write!(f, "{}-{:02}-{:02}", year, month, day)
             }
             Date::YearWeekDay {
                 year,
                 week_in_year,
                 day_in_week,
-            } => write!(f, "{:0>4}-W{:0>2}-{}", year, week_in_year, day_in_week),
-            Date::YearDay { year, day_in_year } => write!(f, "{:0>4}-{:0>3}", year, day_in_year),
+            } => {
+                let year = format::format_year(*year as i64);
+                write!(f, "{}-W{:02}-{}", year, week_in_year, day_in_week)
+            }
+            Date::YearDay { year, day_in_year } => {
+                let year = format::format_year(*year as i64);
+                write!(f, "{}-{:03}", year, day_in_year)
+            }
         }
     }
 }
@@ -66,16 +317,113 @@ pub struct Time {
     pub timezone: TimeZone,
 }
 
+impl Time {
+    /// Returns true if all components are within their ranges.
+    ///
+    /// A second of 60 denotes a leap second, which is only valid at
+    /// 23:59:60 UTC. For named zones, whose offset depends on the date, only
+    /// the minute is checked.
+    pub fn is_valid(&self) -> bool {
+        let in_ranges = self.hour < 24
+            && self.minute < 60
+            && self.second <= 60
+            && self.milli < 1000
+            && self.micro < 1000
+            && self.nano < 1000;
+        if !in_ranges || !self.is_leap_second() {
+            return in_ranges;
+        }
+
+        match self.timezone.fixed_offset_seconds() {
+            Some(offset) => {
+                let minute_of_day = self.hour as i64 * 60 + self.minute as i64 - offset / 60;
+                minute_of_day.rem_euclid(24 * 60) == 24 * 60 - 1
+            }
+            None => self.minute == 59,
+        }
+    }
+
+    /// Returns true if the time falls into a leap second, e.g. 23:59:60.
+    pub fn is_leap_second(&self) -> bool {
+        self.second == 60
+    }
+
+    /// Nanoseconds since midnight (wall clock time).
+    ///
+    /// A leap second is counted as the following second, so in arithmetic
+    /// 23:59:60 behaves like midnight of the next day.
+    pub fn nanos_of_day(&self) -> i64 {
+        let seconds = self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64;
+        seconds * NANOS_PER_SECOND
+            + self.milli as i64 * 1_000_000
+            + self.micro as i64 * 1_000
+            + self.nano as i64
+    }
+
+    /// Creates a time from nanoseconds since midnight, which have to be in
+    /// the range of a single day.
+    pub fn from_nanos_of_day(nanos: i64, timezone: TimeZone) -> Option<Self> {
+        if !(0..SECONDS_PER_DAY * NANOS_PER_SECOND).contains(&nanos) {
+            return None;
+        }
+
+        let seconds = nanos / NANOS_PER_SECOND;
+        let subseconds = nanos % NANOS_PER_SECOND;
+        Some(Time {
+            hour: (seconds / 3600) as HourNumber,
+            minute: (seconds % 3600 / 60) as MinuteNumber,
+            second: (seconds % 60) as SecondNumber,
+            milli: (subseconds / 1_000_000) as MilliNumber,
+            micro: (subseconds / 1_000 % 1_000) as MicroNumber,
+            nano: (subseconds % 1_000) as NanoNumber,
+            timezone,
+        })
+    }
+}
+
+impl Time {
+    /// Nanoseconds of the current second.
+    pub fn subsecond_nanos(&self) -> u32 {
+        self.milli as u32 * 1_000_000 + self.micro as u32 * 1_000 + self.nano as u32
+    }
+
+    /// Writes the wall clock time, with as many fractional digits as needed.
+    fn fmt_wall_clock(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        let nanos = self.subsecond_nanos();
+        if nanos != 0 {
+            let fraction = format!("{:09}", nanos);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `Z`, an offset like `+01:00`, or the name of the zone. Nothing is
+/// written for unspecified zones.
+impl std::fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeZone::Utc => write!(f, "Z"),
+            TimeZone::Unspecified => Ok(()),
+            TimeZone::Offset { hours, minutes } => {
+                let sign = if *hours < 0 || *minutes < 0 { '-' } else { '+' };
+                write!(f, "{}{:02}:{:02}", sign, hours.abs(), minutes.abs())
+            }
+            TimeZone::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Writes the time in ISO 8601 format, e.g. `12:30:15.25+01:00`. Named zones
+/// are written in brackets, as in RFC 9557, e.g. `12:30:15[Europe/Paris]`.
 impl std::fmt::Display for Time {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let subseconds: f64 = (self.milli as f64 / 1000.0)
-            + (self.micro as f64 / 1000.0 / 1000.0)
-            + (self.nano as f64 / 1000.0 / 1000.0 / 1000.0);
-        writeln!(
-            f,
-            "{:0>2}:{:0>2}:{:0>2}.{}",
-            self.hour, self.minute, self.second, subseconds
-        )
+        self.fmt_wall_clock(f)?;
+        match &self.timezone {
+            TimeZone::Named(name) => write!(f, "[{}]", name),
+            timezone => write!(f, "{}", timezone),
+        }
     }
 }
 
@@ -87,7 +435,25 @@ pub enum DateTime {
     Full { date: Date, time: Time },
 }
 
+/// The sub-type of a `DateTime` value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DateTimeType {
+    Date,
+    Time,
+    Full,
+}
+
 impl DateTime {
+    /// Returns the sub-type of the date time.
+    pub fn datetime_type(&self) -> DateTimeType {
+        match self {
+            DateTime::Date(_) => DateTimeType::Date,
+            DateTime::Time(_) => DateTimeType::Time,
+            DateTime::Full { .. } => DateTimeType::Full,
+        }
+    }
+
     pub fn ymd(year: YearNumber, month: MonthNumber, day: DayNumber) -> Self {
         Self::Date(Date::YearMonthDay { year, month, day })
     }
@@ -128,14 +494,262 @@ impl DateTime {
     }
 }
 
-impl std::fmt::Display for DateTime {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Date {
+    /// Converts the date to the representation used by `template`.
+    pub(crate) fn to_representation_of(&self, template: &Date) -> Option<Self> {
+        match template {
+            Date::YearMonthDay { .. } => self.to_year_month_day(),
+            Date::YearDay { .. } => self.to_year_day(),
+            Date::YearWeekDay { .. } => self.to_year_week_day(),
+        }
+    }
+
+    /// Adds months, clamping the day to the length of the resulting month,
+    /// e.g. 2021-01-31 plus one month is 2021-02-28.
+    fn add_months(&self, months: i64) -> Option<Self> {
+        let (year, month, day) = calendar::civil_from_days(self.to_epoch_days()?)?;
+        let month_index = year
+            .checked_mul(12)?
+            .checked_add(month - 1)?
+            .checked_add(months)?;
+        let year = month_index.div_euclid(12);
+        let month = month_index.rem_euclid(12) + 1;
+        let day = day.min(calendar::days_in_month(year, month)?);
+        Some(Date::YearMonthDay {
+            year: YearNumber::try_from(year).ok()?,
+            month: month as MonthNumber,
+            day: day as DayNumber,
+        })
+    }
+
+    fn add_nominal(&self, duration: &Duration) -> Option<Self> {
+        let shifted = self.add_months(duration.months)?;
+        let days = shifted.to_epoch_days()?.checked_add(duration.days)?;
+        Date::from_epoch_days(days)
+    }
+}
+
+impl DateTime {
+    /// Adds a duration. Months are added first, then days, then the exact
+    /// part of the duration.
+    ///
+    /// Dates can only be shifted by whole days, and times (without a date)
+    /// only by exact durations, wrapping around midnight. Returns `None` if
+    /// the duration does not apply or the result is out of range.
+    pub fn checked_add(&self, duration: &Duration) -> Option<DateTime> {
         match self {
-            DateTime::Date(d) => writeln!(f, "{}", d.to_string()),
-            DateTime::Time(t) => writeln!(f, "{}", t.to_string()),
+            DateTime::Date(date) => {
+                if duration.total_nanos() != 0 {
+                    return None;
+                }
+                let result = date.add_nominal(duration)?;
+                Some(DateTime::Date(result.to_representation_of(date)?))
+            }
+            DateTime::Time(time) => {
+                if !duration.is_exact() {
+                    return None;
+                }
+                let day = (SECONDS_PER_DAY * NANOS_PER_SECOND) as i128;
+                let nanos = (time.nanos_of_day() as i128 + duration.total_nanos()).rem_euclid(day);
+                let result = Time::from_nanos_of_day(nanos as i64, time.timezone.clone())?;
+                Some(DateTime::Time(result))
+            }
             DateTime::Full { date, time } => {
-                writeln!(f, "{}T{}", date.to_string(), time.to_string())
+                // The nominal part moves the wall clock time, the exact part
+                // is added to the instant, which matters across DST changes.
+                let shifted = DateTime::Full {
+                    date: date.add_nominal(duration)?,
+                    time: time.clone(),
+                };
+                let nanos = shifted.utc_nanos()?.checked_add(duration.total_nanos())?;
+                DateTime::from_utc_nanos(nanos, &time.timezone, date)
+            }
+        }
+    }
+
+    pub fn checked_sub(&self, duration: &Duration) -> Option<DateTime> {
+        self.checked_add(&duration.checked_neg()?)
+    }
+
+    /// Returns the duration from `earlier` to `self`.
+    ///
+    /// Between dates the result is a number of days. Between full date times
+    /// the time zone offsets are taken into account, and the result consists
+    /// of whole days and the remaining seconds. Between times, the result is
+    /// exact. Returns `None` for mixed kinds of date times or invalid dates.
+    pub fn duration_since(&self, earlier: &DateTime) -> Option<Duration> {
+        match (self, earlier) {
+            (DateTime::Date(a), DateTime::Date(b)) => {
+                Some(Duration::days(a.to_epoch_days()? - b.to_epoch_days()?))
+            }
+            (DateTime::Time(a), DateTime::Time(b)) => {
+                let offsets =
+                    a.timezone.fixed_offset_seconds()? - b.timezone.fixed_offset_seconds()?;
+                let nanos = a.nanos_of_day() - b.nanos_of_day() - offsets * NANOS_PER_SECOND;
+                Some(Duration::new(0, 0, 0, nanos))
+            }
+            (DateTime::Full { .. }, DateTime::Full { .. }) => {
+                let nanos = self.utc_nanos()? - earlier.utc_nanos()?;
+                let day = (SECONDS_PER_DAY * NANOS_PER_SECOND) as i128;
+                let days = i64::try_from(nanos / day).ok()?;
+                Duration::from_nanos(0, days, nanos % day)
+            }
+            _ => None,
+        }
+    }
+
+    /// Wall clock nanoseconds since 1970-01-01T00:00:00 for full date times.
+    fn local_nanos(&self) -> Option<i128> {
+        match self {
+            DateTime::Full { date, time } => Some(
+                date.to_epoch_days()? as i128 * (SECONDS_PER_DAY * NANOS_PER_SECOND) as i128
+                    + time.nanos_of_day() as i128,
+            ),
+            _ => None,
+        }
+    }
+
+    /// Offset from UTC in seconds. For full date times in named zones, this
+    /// is the offset in effect at that date and time. Dates have no offset.
+    pub fn offset_seconds(&self) -> Option<i64> {
+        match self {
+            DateTime::Date(_) => None,
+            DateTime::Time(time) => time.timezone.fixed_offset_seconds(),
+            DateTime::Full { time, .. } => {
+                let local_seconds =
+                    i64::try_from(self.local_nanos()?.div_euclid(NANOS_PER_SECOND as i128)).ok()?;
+                time.timezone.offset_seconds_at_local(local_seconds)
             }
         }
     }
+
+    /// Nanoseconds since the epoch in UTC for full date times.
+    ///
+    /// Returns `None` for wall clock times that do not exist in a named zone
+    /// because of a DST transition, or if the zone is unknown.
+    pub(crate) fn utc_nanos(&self) -> Option<i128> {
+        let offset = self.offset_seconds()?;
+        Some(self.local_nanos()? - offset as i128 * NANOS_PER_SECOND as i128)
+    }
+
+    /// Creates a full date time in `timezone` from nanoseconds since the epoch
+    /// in UTC, using the date representation of `template`.
+    fn from_utc_nanos(utc_nanos: i128, timezone: &TimeZone, template: &Date) -> Option<DateTime> {
+        let utc_seconds = i64::try_from(utc_nanos.div_euclid(NANOS_PER_SECOND as i128)).ok()?;
+        let offset = timezone.offset_seconds_at(utc_seconds)?;
+        let local_nanos = utc_nanos + offset as i128 * NANOS_PER_SECOND as i128;
+        let day = (SECONDS_PER_DAY * NANOS_PER_SECOND) as i128;
+        let date = Date::from_epoch_days(i64::try_from(local_nanos.div_euclid(day)).ok()?)?;
+        let time = Time::from_nanos_of_day(local_nanos.rem_euclid(day) as i64, timezone.clone())?;
+        Some(DateTime::Full {
+            date: date.to_representation_of(template)?,
+            time,
+        })
+    }
+
+    /// Converts the date time to another time zone, keeping the instant it
+    /// denotes. The date keeps its representation.
+    ///
+    /// Full date times can be converted to any zone, taking daylight saving
+    /// time into account for named zones. Times without a date can only be
+    /// converted between fixed offsets, and wrap around midnight. Dates cannot
+    /// be converted.
+    pub fn to_timezone(&self, timezone: &TimeZone) -> Option<DateTime> {
+        match self {
+            DateTime::Date(_) => None,
+            DateTime::Time(time) => {
+                let shift =
+                    timezone.fixed_offset_seconds()? - time.timezone.fixed_offset_seconds()?;
+                let day = SECONDS_PER_DAY * NANOS_PER_SECOND;
+                let nanos = (time.nanos_of_day() + shift * NANOS_PER_SECOND).rem_euclid(day);
+                Some(DateTime::Time(Time::from_nanos_of_day(
+                    nanos,
+                    timezone.clone(),
+                )?))
+            }
+            DateTime::Full { date, .. } => {
+                DateTime::from_utc_nanos(self.utc_nanos()?, timezone, date)
+            }
+        }
+    }
+
+    /// Converts the date time to UTC. See `to_timezone`.
+    pub fn to_utc(&self) -> Option<DateTime> {
+        self.to_timezone(&TimeZone::Utc)
+    }
+
+    /// Creates a full date time in UTC from a Unix timestamp in nanoseconds.
+    pub fn from_epoch_nanos(nanos: i128) -> Option<DateTime> {
+        let template = Date::YearMonthDay {
+            year: 1970,
+            month: 1,
+            day: 1,
+        };
+        DateTime::from_utc_nanos(nanos, &TimeZone::Utc, &template)
+    }
+
+    /// Returns the Unix timestamp in nanoseconds of a full date time. Like
+    /// Unix time, it does not count leap seconds.
+    pub fn epoch_nanos(&self) -> Option<i128> {
+        self.utc_nanos()
+    }
+}
+
+/// Unit of a Unix timestamp, i.e. of the time since 1970-01-01T00:00:00Z.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EpochUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl EpochUnit {
+    /// Number of nanoseconds in one unit.
+    pub fn nanos(&self) -> i64 {
+        match self {
+            EpochUnit::Seconds => NANOS_PER_SECOND,
+            EpochUnit::Milliseconds => 1_000_000,
+            EpochUnit::Microseconds => 1_000,
+            EpochUnit::Nanoseconds => 1,
+        }
+    }
+
+    /// Number of decimal places of a nanosecond in this unit.
+    pub(crate) fn nanosecond_scale(&self) -> i64 {
+        match self {
+            EpochUnit::Seconds => 9,
+            EpochUnit::Milliseconds => 6,
+            EpochUnit::Microseconds => 3,
+            EpochUnit::Nanoseconds => 0,
+        }
+    }
+}
+
+/// Writes the date time in ISO 8601 format, which `Parser` reads back.
+///
+/// Full date times in named zones are written with the offset in effect and
+/// the zone name, e.g. `2021-07-01T12:00:00+02:00[Europe/Luxembourg]`.
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateTime::Date(d) => write!(f, "{}", d),
+            DateTime::Time(t) => write!(f, "{}", t),
+            DateTime::Full { date, time } => match &time.timezone {
+                TimeZone::Named(name) => {
+                    write!(f, "{}T", date)?;
+                    time.fmt_wall_clock(f)?;
+                    if let Some(offset) = self
+                        .offset_seconds()
+                        .and_then(TimeZone::from_offset_seconds)
+                    {
+                        write!(f, "{}", offset)?;
+                    }
+                    write!(f, "[{}]", name)
+                }
+                _ => write!(f, "{}T{}", date, time),
+            },
+        }
+    }
 }
@@ -0,0 +1,158 @@
+//! Proleptic Gregorian calendar arithmetic.
+//!
+//! Days are counted relative to the Unix epoch, i.e. 1970-01-01 is day 0.
+//! Weekdays follow ISO 8601 and range from 1 (Monday) to 7 (Sunday).
+//!
+//! Conversions return `None` when a result does not fit into an `i64`.
+//!
+//! With the `chrono_dates` feature, conversions between days and calendar
+//! dates are delegated to `chrono` within the range of `chrono::NaiveDate`.
+
+pub(crate) fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) fn days_in_year(year: i64) -> i64 {
+    if is_leap_year(year) {
+        366
+    } else {
+        365
+    }
+}
+
+pub(crate) fn days_in_month(year: i64, month: i64) -> Option<i64> {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => Some(31),
+        4 | 6 | 9 | 11 => Some(30),
+        2 if is_leap_year(year) => Some(29),
+        2 => Some(28),
+        _ => None,
+    }
+}
+
+/// Days since the epoch for a (valid) calendar date.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    #[cfg(feature = "chrono_dates")]
+    {
+        if let Some(days) = chrono_calendar::days_from_civil(year, month, day) {
+            return Some(days);
+        }
+    }
+    proleptic_days_from_civil(year, month, day)
+}
+
+/// Calendar date for a number of days since the epoch.
+pub(crate) fn civil_from_days(days: i64) -> Option<(i64, i64, i64)> {
+    #[cfg(feature = "chrono_dates")]
+    {
+        if let Some(date) = chrono_calendar::civil_from_days(days) {
+            return Some(date);
+        }
+    }
+    proleptic_civil_from_days(days)
+}
+
+#[cfg(feature = "chrono_dates")]
+mod chrono_calendar {
+    use chrono::{Datelike, NaiveDate};
+    use std::convert::TryFrom;
+
+    /// `NaiveDate::num_days_from_ce` of 1970-01-01.
+    const EPOCH_DAYS_FROM_CE: i64 = 719_163;
+
+    pub(super) fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+        let date = NaiveDate::from_ymd_opt(
+            i32::try_from(year).ok()?,
+            u32::try_from(month).ok()?,
+            u32::try_from(day).ok()?,
+        )?;
+        Some(date.num_days_from_ce() as i64 - EPOCH_DAYS_FROM_CE)
+    }
+
+    pub(super) fn civil_from_days(days: i64) -> Option<(i64, i64, i64)> {
+        let days_from_ce = i32::try_from(days.checked_add(EPOCH_DAYS_FROM_CE)?).ok()?;
+        let date = NaiveDate::from_num_days_from_ce_opt(days_from_ce)?;
+        Some((date.year() as i64, date.month() as i64, date.day() as i64))
+    }
+}
+
+/// Based on Howard Hinnant's `days_from_civil` algorithm.
+fn proleptic_days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    let y = if month <= 2 {
+        year.checked_sub(1)?
+    } else {
+        year
+    };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era.checked_mul(146_097)?
+        .checked_add(doe)?
+        .checked_sub(719_468)
+}
+
+/// Based on Howard Hinnant's `civil_from_days` algorithm.
+fn proleptic_civil_from_days(days: i64) -> Option<(i64, i64, i64)> {
+    let z = days.checked_add(719_468)?;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = era
+        .checked_mul(400)?
+        .checked_add(yoe)?
+        .checked_add(if month <= 2 { 1 } else { 0 })?;
+    Some((year, month, day))
+}
+
+/// ISO weekday of a day since the epoch. 1970-01-01 was a Thursday.
+pub(crate) fn weekday(days: i64) -> i64 {
+    (days.rem_euclid(7) + 3) % 7 + 1
+}
+
+pub(crate) fn days_from_ordinal(year: i64, day_in_year: i64) -> Option<i64> {
+    days_from_civil(year, 1, 1)?.checked_add(day_in_year - 1)
+}
+
+/// Ordinal day in the calendar year of a day since the epoch, from 1.
+pub(crate) fn day_in_year(days: i64) -> Option<i64> {
+    let (year, _, _) = civil_from_days(days)?;
+    Some(days - days_from_civil(year, 1, 1)? + 1)
+}
+
+/// Days since the epoch of the Monday starting week 1 of an ISO week-year.
+///
+/// Week 1 is the week containing the 4th of January.
+fn iso_week_one_start(year: i64) -> Option<i64> {
+    let jan4 = days_from_civil(year, 1, 4)?;
+    jan4.checked_sub(weekday(jan4) - 1)
+}
+
+pub(crate) fn iso_weeks_in_year(year: i64) -> Option<i64> {
+    Some((iso_week_one_start(year.checked_add(1)?)? - iso_week_one_start(year)?) / 7)
+}
+
+pub(crate) fn days_from_iso_week(year: i64, week: i64, day_in_week: i64) -> Option<i64> {
+    iso_week_one_start(year)?
+        .checked_add((week - 1).checked_mul(7)?)?
+        .checked_add(day_in_week - 1)
+}
+
+/// ISO week date (week-year, week, weekday) of a day since the epoch.
+pub(crate) fn iso_week_from_days(days: i64) -> Option<(i64, i64, i64)> {
+    let (year, _, _) = civil_from_days(days)?;
+    let week_year = if days >= iso_week_one_start(year.checked_add(1)?)? {
+        year + 1
+    } else if days < iso_week_one_start(year)? {
+        year.checked_sub(1)?
+    } else {
+        year
+    };
+    let week = (days - iso_week_one_start(week_year)?) / 7 + 1;
+    Some((week_year, week, weekday(days)))
+}
@@ -0,0 +1,186 @@
+//! Conversions between `chrono` types and date time primitives.
+//!
+//! Naive dates, times and date times convert to values with an unspecified
+//! time zone, and back to their wall clock time. `chrono::DateTime<Tz>`
+//! keeps its offset, and with the `timezones` feature,
+//! `chrono::DateTime<chrono_tz::Tz>` keeps the zone name. Leap seconds are
+//! kept in both directions.
+
+use super::*;
+use crate::errors::CoercionError;
+use chrono::{Datelike, Offset, Timelike};
+
+fn not_representable(what: &str) -> CoercionError {
+    CoercionError::DomainError(format!("Cannot represent the {} in chrono.", what))
+}
+
+impl From<chrono::NaiveDate> for Date {
+    fn from(date: chrono::NaiveDate) -> Self {
+        Date::YearMonthDay {
+            year: date.year(),
+            month: date.month() as MonthNumber,
+            day: date.day() as DayNumber,
+        }
+    }
+}
+
+impl TryFrom<&Date> for chrono::NaiveDate {
+    type Error = CoercionError;
+
+    fn try_from(date: &Date) -> Result<Self, Self::Error> {
+        date.to_year_month_day()
+            .and_then(|ymd| match ymd {
+                Date::YearMonthDay { year, month, day } => {
+                    chrono::NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| not_representable("date"))
+    }
+}
+
+/// Converts the wall clock time. The time zone is unspecified.
+impl From<chrono::NaiveTime> for Time {
+    fn from(time: chrono::NaiveTime) -> Self {
+        // chrono represents leap seconds as nanoseconds beyond one second.
+        let (second, nanos) = if time.nanosecond() >= NANOS_PER_SECOND as u32 {
+            (60, time.nanosecond() - NANOS_PER_SECOND as u32)
+        } else {
+            (time.second(), time.nanosecond())
+        };
+        Time {
+            hour: time.hour() as HourNumber,
+            minute: time.minute() as MinuteNumber,
+            second: second as SecondNumber,
+            milli: (nanos / 1_000_000) as MilliNumber,
+            micro: (nanos / 1_000 % 1_000) as MicroNumber,
+            nano: (nanos % 1_000) as NanoNumber,
+            timezone: TimeZone::Unspecified,
+        }
+    }
+}
+
+/// Converts the wall clock time, without the time zone.
+impl TryFrom<&Time> for chrono::NaiveTime {
+    type Error = CoercionError;
+
+    fn try_from(time: &Time) -> Result<Self, Self::Error> {
+        if !time.is_valid() {
+            return Err(not_representable("time"));
+        }
+        let (second, nanos) = if time.is_leap_second() {
+            (59, time.subsecond_nanos() + NANOS_PER_SECOND as u32)
+        } else {
+            (time.second as u32, time.subsecond_nanos())
+        };
+        chrono::NaiveTime::from_hms_nano_opt(time.hour as u32, time.minute as u32, second, nanos)
+            .ok_or_else(|| not_representable("time"))
+    }
+}
+
+/// Converts to a full date time with an unspecified time zone.
+impl From<chrono::NaiveDateTime> for DateTime {
+    fn from(datetime: chrono::NaiveDateTime) -> Self {
+        DateTime::Full {
+            date: Date::from(datetime.date()),
+            time: Time::from(datetime.time()),
+        }
+    }
+}
+
+/// Converts the wall clock date and time of a full date time.
+impl TryFrom<&DateTime> for chrono::NaiveDateTime {
+    type Error = CoercionError;
+
+    fn try_from(datetime: &DateTime) -> Result<Self, Self::Error> {
+        match datetime {
+            DateTime::Full { date, time } => Ok(chrono::NaiveDateTime::new(
+                chrono::NaiveDate::try_from(date)?,
+                chrono::NaiveTime::try_from(time)?,
+            )),
+            _ => Err(not_representable("date time without a date and a time")),
+        }
+    }
+}
+
+/// Converts to a full date time with the offset of `datetime`.
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for DateTime {
+    fn from(datetime: chrono::DateTime<Tz>) -> Self {
+        let offset = datetime.offset().fix().local_minus_utc() as i64;
+        let mut time = Time::from(datetime.naive_local().time());
+        // Offsets in chrono are always within a day.
+        time.timezone = TimeZone::from_offset_seconds(offset).unwrap_or(TimeZone::Utc);
+        DateTime::Full {
+            date: Date::from(datetime.naive_local().date()),
+            time,
+        }
+    }
+}
+
+impl TryFrom<&DateTime> for chrono::DateTime<chrono::FixedOffset> {
+    type Error = CoercionError;
+
+    fn try_from(datetime: &DateTime) -> Result<Self, Self::Error> {
+        let naive = chrono::NaiveDateTime::try_from(datetime)?;
+        datetime
+            .offset_seconds()
+            .and_then(|offset| chrono::FixedOffset::east_opt(offset as i32))
+            .and_then(|offset| naive.and_local_timezone(offset).single())
+            .ok_or_else(|| not_representable("offset"))
+    }
+}
+
+impl TryFrom<&DateTime> for chrono::DateTime<chrono::Utc> {
+    type Error = CoercionError;
+
+    fn try_from(datetime: &DateTime) -> Result<Self, Self::Error> {
+        chrono::DateTime::<chrono::FixedOffset>::try_from(datetime)
+            .map(|datetime| datetime.with_timezone(&chrono::Utc))
+    }
+}
+
+impl TryFrom<&DateTime> for chrono::DateTime<chrono::Local> {
+    type Error = CoercionError;
+
+    fn try_from(datetime: &DateTime) -> Result<Self, Self::Error> {
+        chrono::DateTime::<chrono::FixedOffset>::try_from(datetime)
+            .map(|datetime| datetime.with_timezone(&chrono::Local))
+    }
+}
+
+#[cfg(feature = "timezones")]
+impl DateTime {
+    /// Converts a `chrono_tz` date time, keeping the name of the zone.
+    pub fn from_chrono_tz(datetime: chrono::DateTime<chrono_tz::Tz>) -> Self {
+        let timezone = TimeZone::Named(datetime.timezone().name().to_string());
+        match DateTime::from(datetime) {
+            DateTime::Full { date, mut time } => {
+                time.timezone = timezone;
+                DateTime::Full { date, time }
+            }
+            other => other,
+        }
+    }
+}
+
+/// Converts full date times in named zones.
+#[cfg(feature = "timezones")]
+impl TryFrom<&DateTime> for chrono::DateTime<chrono_tz::Tz> {
+    type Error = CoercionError;
+
+    fn try_from(datetime: &DateTime) -> Result<Self, Self::Error> {
+        let zone: chrono_tz::Tz = match datetime {
+            DateTime::Full {
+                time:
+                    Time {
+                        timezone: TimeZone::Named(name),
+                        ..
+                    },
+                ..
+            } => name.parse().map_err(|_| not_representable("time zone"))?,
+            _ => return Err(not_representable("date time without a named zone")),
+        };
+        chrono::DateTime::<chrono::FixedOffset>::try_from(datetime)
+            .map(|datetime| datetime.with_timezone(&zone))
+    }
+}
@@ -0,0 +1,320 @@
+use crate::errors::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+pub(crate) const NANOS_PER_SECOND: i64 = 1_000_000_000;
+pub(crate) const SECONDS_PER_DAY: i64 = 86_400;
+
+/// *Primitive*: A time interval following the ISO 8601 duration model.
+///
+/// Months (and years) and days are nominal units, whose length depends on
+/// the date they are applied to. Seconds are exact. The sub-second part is
+/// kept in `nanos`, which is always in the range `0..1_000_000_000`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Duration {
+    pub months: i64,
+    pub days: i64,
+    pub seconds: i64,
+    pub nanos: u32,
+}
+
+impl Duration {
+    pub fn new(months: i64, days: i64, seconds: i64, nanos: i64) -> Self {
+        Self {
+            months,
+            days,
+            seconds: seconds + nanos.div_euclid(NANOS_PER_SECOND),
+            nanos: nanos.rem_euclid(NANOS_PER_SECOND) as u32,
+        }
+    }
+
+    pub fn months(months: i64) -> Self {
+        Self::new(months, 0, 0, 0)
+    }
+
+    pub fn days(days: i64) -> Self {
+        Self::new(0, days, 0, 0)
+    }
+
+    pub fn seconds(seconds: i64) -> Self {
+        Self::new(0, 0, seconds, 0)
+    }
+
+    /// The exact (sub-day) part of the duration in nanoseconds.
+    pub fn total_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND as i128 + self.nanos as i128
+    }
+
+    pub(crate) fn from_nanos(months: i64, days: i64, nanos: i128) -> Option<Self> {
+        let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND as i128)).ok()?;
+        let nanos = nanos.rem_euclid(NANOS_PER_SECOND as i128) as u32;
+        Some(Self {
+            months,
+            days,
+            seconds,
+            nanos,
+        })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.months == 0 && self.days == 0 && self.seconds == 0 && self.nanos == 0
+    }
+
+    /// Returns true if the duration has no month or day components.
+    pub fn is_exact(&self) -> bool {
+        self.months == 0 && self.days == 0
+    }
+
+    pub fn checked_add(&self, other: &Duration) -> Option<Duration> {
+        Self::from_nanos(
+            self.months.checked_add(other.months)?,
+            self.days.checked_add(other.days)?,
+            self.total_nanos().checked_add(other.total_nanos())?,
+        )
+    }
+
+    pub fn checked_neg(&self) -> Option<Duration> {
+        Self::from_nanos(
+            self.months.checked_neg()?,
+            self.days.checked_neg()?,
+            -self.total_nanos(),
+        )
+    }
+
+    pub fn checked_sub(&self, other: &Duration) -> Option<Duration> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    pub fn checked_mul(&self, factor: i64) -> Option<Duration> {
+        Self::from_nanos(
+            self.months.checked_mul(factor)?,
+            self.days.checked_mul(factor)?,
+            self.total_nanos().checked_mul(factor as i128)?,
+        )
+    }
+}
+
+/// Durations are only ordered if all of their components are ordered the
+/// same way, since e.g. `P1M` and `P30D` are not comparable in general.
+impl PartialOrd for Duration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let orderings = [
+            self.months.cmp(&other.months),
+            self.days.cmp(&other.days),
+            self.total_nanos().cmp(&other.total_nanos()),
+        ];
+
+        if orderings.iter().all(|o| *o != Ordering::Greater) {
+            Some(
+                orderings
+                    .iter()
+                    .fold(Ordering::Equal, |acc, o| acc.then(*o)),
+            )
+        } else if orderings.iter().all(|o| *o != Ordering::Less) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+fn write_component(
+    f: &mut std::fmt::Formatter<'_>,
+    value: i64,
+    negate: bool,
+    designator: char,
+) -> std::fmt::Result {
+    if value != 0 {
+        let value = if negate { -value } else { value };
+        write!(f, "{}{}", value, designator)?;
+    }
+    Ok(())
+}
+
+/// Formats the duration in ISO 8601 notation, e.g. `P1Y2M3DT4H5M6.5S`. If
+/// all components are negative, the duration is prefixed with a minus sign.
+impl std::fmt::Display for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "PT0S");
+        }
+
+        let total_nanos = self.total_nanos();
+        let negative = self.months <= 0 && self.days <= 0 && total_nanos <= 0;
+        if negative {
+            write!(f, "-")?;
+        }
+        write!(f, "P")?;
+
+        write_component(f, self.months / 12, negative, 'Y')?;
+        write_component(f, self.months % 12, negative, 'M')?;
+        write_component(f, self.days, negative, 'D')?;
+
+        if total_nanos != 0 {
+            write!(f, "T")?;
+            let sign = if total_nanos < 0 && !negative {
+                "-"
+            } else {
+                ""
+            };
+            let abs_nanos = total_nanos.abs();
+            let abs_seconds = (abs_nanos / NANOS_PER_SECOND as i128) as i64;
+            let subseconds = (abs_nanos % NANOS_PER_SECOND as i128) as u32;
+
+            let hours = abs_seconds / 3600;
+            let minutes = abs_seconds % 3600 / 60;
+            let seconds = abs_seconds % 60;
+            if hours != 0 {
+                write!(f, "{}{}H", sign, hours)?;
+            }
+            if minutes != 0 {
+                write!(f, "{}{}M", sign, minutes)?;
+            }
+            if seconds != 0 || subseconds != 0 {
+                write!(f, "{}{}", sign, seconds)?;
+                if subseconds != 0 {
+                    let digits = format!("{:09}", subseconds);
+                    write!(f, ".{}", digits.trim_end_matches('0'))?;
+                }
+                write!(f, "S")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_duration(source: &str) -> ParsingError {
+    ParsingError::cannot_parse(source).with_expected("an ISO 8601 duration like P1DT2H")
+}
+
+fn out_of_range(source: &str, component: &str) -> ParsingError {
+    invalid_duration(source).with_cause(ParsingCause::OutOfRange(component.to_string()))
+}
+
+/// Parses a decimal number of a duration component into an integer part and
+/// a fraction of 10^9.
+fn parse_component(s: &str, source: &str, component: &str) -> Result<(i64, i64), ParsingError> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let mut parts = digits.splitn(2, ['.', ',']);
+    let integer = parts.next().unwrap_or("");
+    let fraction = parts.next().unwrap_or("");
+    if integer.is_empty() || !integer.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid_duration(source));
+    }
+    if fraction.len() > 9 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid_duration(source));
+    }
+
+    // Only digits are left, so the integer part can only be too large.
+    let integer: i64 = integer
+        .parse()
+        .map_err(|_| out_of_range(source, component))?;
+    let fraction: i64 = format!("{:0<9}", fraction)
+        .parse()
+        .map_err(|_| invalid_duration(source))?;
+    if negative {
+        Ok((-integer, -fraction))
+    } else {
+        Ok((integer, fraction))
+    }
+}
+
+/// Parses ISO 8601 durations, e.g. `P3DT4H`, `P2W`, `-P1Y` or `PT0.5S`.
+///
+/// Fractions are allowed for the seconds component only.
+impl FromStr for Duration {
+    type Err = ParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || invalid_duration(s);
+
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let rest = rest.strip_prefix('P').ok_or_else(error)?;
+        let (date_part, time_part) = match rest.find('T') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+        if date_part.is_empty() && time_part.is_none_or(|t| t.is_empty()) {
+            return Err(error());
+        }
+
+        let mut duration = Duration::default();
+        let mut nanos: i128 = 0;
+
+        for (part, designators, is_time) in [
+            (date_part, "YMWD", false),
+            (time_part.unwrap_or(""), "HMS", true),
+        ] {
+            let mut remaining = part;
+            let mut last_designator = 0;
+            while !remaining.is_empty() {
+                let end = remaining
+                    .find(|c: char| c.is_ascii_alphabetic())
+                    .ok_or_else(error)?;
+                let designator = remaining[end..].chars().next().ok_or_else(error)?;
+                let position = designators.find(designator).ok_or_else(error)? + 1;
+                // Components have to appear in order and at most once.
+                if position <= last_designator {
+                    return Err(error());
+                }
+                last_designator = position;
+
+                let component = match (is_time, designator) {
+                    (false, 'Y') => "years",
+                    (false, 'M') => "months",
+                    (false, 'W') => "weeks",
+                    (false, 'D') => "days",
+                    (true, 'H') => "hours",
+                    (true, 'M') => "minutes",
+                    _ => "seconds",
+                };
+                let (value, fraction) = parse_component(&remaining[..end], s, component)?;
+                if fraction != 0 && !(is_time && designator == 'S') {
+                    return Err(error());
+                }
+
+                let add = |total: i64, factor: i64| {
+                    value
+                        .checked_mul(factor)
+                        .and_then(|value| total.checked_add(value))
+                        .ok_or_else(|| out_of_range(s, component))
+                };
+                // Time components are added up as i128 nanoseconds, which
+                // cannot overflow for i64 components.
+                match (is_time, designator) {
+                    (false, 'Y') => duration.months = add(duration.months, 12)?,
+                    (false, 'M') => duration.months = add(duration.months, 1)?,
+                    (false, 'W') => duration.days = add(duration.days, 7)?,
+                    (false, 'D') => duration.days = add(duration.days, 1)?,
+                    (true, 'H') => nanos += value as i128 * 3600 * NANOS_PER_SECOND as i128,
+                    (true, 'M') => nanos += value as i128 * 60 * NANOS_PER_SECOND as i128,
+                    (true, 'S') => {
+                        nanos += value as i128 * NANOS_PER_SECOND as i128 + fraction as i128
+                    }
+                    _ => return Err(error()),
+                }
+                remaining = &remaining[end + 1..];
+            }
+        }
+
+        let duration = Duration::from_nanos(duration.months, duration.days, nanos)
+            .ok_or_else(|| out_of_range(s, "seconds"))?;
+        if negative {
+            duration
+                .checked_neg()
+                .ok_or_else(|| out_of_range(s, "duration"))
+        } else {
+            Ok(duration)
+        }
+    }
+}
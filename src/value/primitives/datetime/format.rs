@@ -0,0 +1,675 @@
+use super::*;
+use crate::errors::{FormattingError, ParsingCause, ParsingError};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Specifier {
+    Year,
+    ShortYear,
+    Month,
+    MonthName,
+    ShortMonthName,
+    Day,
+    DayOfYear,
+    IsoYear,
+    IsoWeek,
+    Weekday,
+    WeekdayName,
+    ShortWeekdayName,
+    Hour,
+    Hour12,
+    AmPm,
+    Minute,
+    Second,
+    /// Fractional seconds with a fixed number of digits.
+    Fraction(usize),
+    /// A decimal point and the significant fractional digits, if any.
+    OptionalFraction,
+    Offset,
+    OffsetWithColon,
+    Zone,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Item {
+    Literal(String),
+    Specifier(Specifier),
+}
+
+impl Item {
+    /// Describes what the item reads, for parsing errors.
+    fn describe(&self) -> String {
+        let description = match self {
+            Item::Literal(literal) => return format!("{:?}", literal),
+            Item::Specifier(specifier) => match specifier {
+                Specifier::Year | Specifier::IsoYear => "a year",
+                Specifier::ShortYear => "a two-digit year",
+                Specifier::Month => "a month",
+                Specifier::MonthName | Specifier::ShortMonthName => "a month name",
+                Specifier::Day => "a day",
+                Specifier::DayOfYear => "a day of the year",
+                Specifier::IsoWeek => "a week number",
+                Specifier::Weekday => "a weekday number",
+                Specifier::WeekdayName | Specifier::ShortWeekdayName => "a weekday name",
+                Specifier::Hour | Specifier::Hour12 => "an hour",
+                Specifier::AmPm => "AM or PM",
+                Specifier::Minute => "a minute",
+                Specifier::Second => "a second",
+                Specifier::Fraction(_) | Specifier::OptionalFraction => "fractional seconds",
+                Specifier::Offset | Specifier::OffsetWithColon => "an offset",
+                Specifier::Zone => "a time zone",
+            },
+        };
+        description.to_string()
+    }
+}
+
+/// A strftime-style pattern for formatting dates and times.
+///
+/// Supported specifiers:
+///
+/// | Specifier | Meaning                                       | Example      |
+/// |-----------|-----------------------------------------------|--------------|
+/// | `%Y`      | Year, at least four digits                    | `2021`       |
+/// | `%y`      | Year modulo 100, two digits                   | `21`         |
+/// | `%m`      | Month, two digits                             | `07`         |
+/// | `%B`/`%b` | Month name, full or abbreviated               | `July`/`Jul` |
+/// | `%d`      | Day of the month, two digits                  | `04`         |
+/// | `%j`      | Day of the year, three digits                 | `185`        |
+/// | `%G`      | ISO 8601 week-year                            | `2021`       |
+/// | `%V`      | ISO 8601 week number, two digits              | `26`         |
+/// | `%u`      | Weekday from 1 (Monday) to 7 (Sunday)         | `7`          |
+/// | `%A`/`%a` | Weekday name, full or abbreviated             | `Sunday`/`Sun` |
+/// | `%H`      | Hour (00-23)                                  | `14`         |
+/// | `%I`      | Hour (01-12)                                  | `02`         |
+/// | `%p`      | `AM` or `PM`                                  | `PM`         |
+/// | `%M`      | Minute                                        | `05`         |
+/// | `%S`      | Second (60 for leap seconds)                  | `09`         |
+/// | `%f`      | Nanoseconds, nine digits                      | `250000000`  |
+/// | `%3f`     | Milliseconds (also `%6f` and `%9f`)           | `250`        |
+/// | `%.f`     | Decimal point and significant digits, if any  | `.25`        |
+/// | `%z`      | Offset from UTC                               | `+0200`      |
+/// | `%:z`     | Offset from UTC with a colon                  | `+02:00`     |
+/// | `%Z`      | Zone name, or `%:z` for fixed offsets         | `Europe/Paris` |
+/// | `%F`      | Same as `%Y-%m-%d`                            |              |
+/// | `%T`      | Same as `%H:%M:%S`                            |              |
+/// | `%%`      | A literal `%`                                 |              |
+///
+/// Formatting fails if the pattern needs a component the value does not
+/// have, e.g. an hour for a date.
+///
+/// The same patterns are used for parsing. Numeric fields accept one up to
+/// their full number of digits, so `%d/%m/%Y` reads `4/3/2021`, while
+/// `%Y%m%d` reads `20210304`. Month and weekday names are matched without
+/// regard to case, in full or abbreviated. Two-digit years from 69 onwards
+/// are in the 1900s, the others in the 2000s. Without an offset, the time zone
+/// is unspecified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateTimeFormat {
+    pattern: String,
+    items: Vec<Item>,
+}
+
+impl DateTimeFormat {
+    pub fn new(pattern: &str) -> Result<Self, FormattingError> {
+        let invalid = || FormattingError::InvalidPattern(pattern.to_string());
+        let mut items: Vec<Item> = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                literal.push(c);
+                continue;
+            }
+
+            let specifier = match chars.next().ok_or_else(invalid)? {
+                '%' => {
+                    literal.push('%');
+                    continue;
+                }
+                'Y' => Specifier::Year,
+                'y' => Specifier::ShortYear,
+                'm' => Specifier::Month,
+                'B' => Specifier::MonthName,
+                'b' => Specifier::ShortMonthName,
+                'd' => Specifier::Day,
+                'j' => Specifier::DayOfYear,
+                'G' => Specifier::IsoYear,
+                'V' => Specifier::IsoWeek,
+                'u' => Specifier::Weekday,
+                'A' => Specifier::WeekdayName,
+                'a' => Specifier::ShortWeekdayName,
+                'H' => Specifier::Hour,
+                'I' => Specifier::Hour12,
+                'p' => Specifier::AmPm,
+                'M' => Specifier::Minute,
+                'S' => Specifier::Second,
+                'f' => Specifier::Fraction(9),
+                digits @ ('3' | '6' | '9') if chars.next() == Some('f') => {
+                    Specifier::Fraction(digits as usize - '0' as usize)
+                }
+                '.' if chars.next() == Some('f') => Specifier::OptionalFraction,
+                'z' => Specifier::Offset,
+                ':' if chars.next() == Some('z') => Specifier::OffsetWithColon,
+                'Z' => Specifier::Zone,
+                'F' => {
+                    push_specifier(&mut items, &mut literal, Specifier::Year);
+                    literal.push('-');
+                    push_specifier(&mut items, &mut literal, Specifier::Month);
+                    literal.push('-');
+                    Specifier::Day
+                }
+                'T' => {
+                    push_specifier(&mut items, &mut literal, Specifier::Hour);
+                    literal.push(':');
+                    push_specifier(&mut items, &mut literal, Specifier::Minute);
+                    literal.push(':');
+                    Specifier::Second
+                }
+                _ => return Err(invalid()),
+            };
+            push_specifier(&mut items, &mut literal, specifier);
+        }
+
+        if !literal.is_empty() {
+            items.push(Item::Literal(literal));
+        }
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            items,
+        })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn format(&self, datetime: &DateTime) -> Result<String, FormattingError> {
+        let (date, time) = match datetime {
+            DateTime::Date(date) => (Some(date), None),
+            DateTime::Time(time) => (None, Some(time)),
+            DateTime::Full { date, time } => (Some(date), Some(time)),
+        };
+        let epoch_days = match date {
+            Some(date) => Some(
+                date.to_epoch_days()
+                    .ok_or_else(|| FormattingError::MissingComponent("valid date".to_string()))?,
+            ),
+            None => None,
+        };
+
+        let missing = |component: &str| FormattingError::MissingComponent(component.to_string());
+        let days = || epoch_days.ok_or_else(|| missing("date"));
+        let civil = || calendar::civil_from_days(days()?).ok_or_else(|| missing("valid date"));
+        let iso_week =
+            || calendar::iso_week_from_days(days()?).ok_or_else(|| missing("valid date"));
+        let time = || time.ok_or_else(|| missing("time"));
+        let offset = || match time()?.timezone {
+            TimeZone::Unspecified => Err(missing("offset")),
+            _ => datetime.offset_seconds().ok_or_else(|| missing("offset")),
+        };
+
+        let mut result = String::new();
+        for item in self.items.iter() {
+            let specifier = match item {
+                Item::Literal(literal) => {
+                    result.push_str(literal);
+                    continue;
+                }
+                Item::Specifier(specifier) => specifier,
+            };
+
+            let formatted = match specifier {
+                Specifier::Year => format_year(civil()?.0),
+                Specifier::ShortYear => {
+                    format!("{:02}", civil()?.0.rem_euclid(100))
+                }
+                Specifier::Month => format!("{:02}", civil()?.1),
+                Specifier::MonthName => MONTH_NAMES[civil()?.1 as usize - 1].to_string(),
+                Specifier::ShortMonthName => MONTH_NAMES[civil()?.1 as usize - 1][..3].to_string(),
+                Specifier::Day => format!("{:02}", civil()?.2),
+                Specifier::DayOfYear => {
+                    let day =
+                        calendar::day_in_year(days()?).ok_or_else(|| missing("valid date"))?;
+                    format!("{:03}", day)
+                }
+                Specifier::IsoYear => format_year(iso_week()?.0),
+                Specifier::IsoWeek => format!("{:02}", iso_week()?.1),
+                Specifier::Weekday => calendar::weekday(days()?).to_string(),
+                Specifier::WeekdayName => {
+                    WEEKDAY_NAMES[calendar::weekday(days()?) as usize - 1].to_string()
+                }
+                Specifier::ShortWeekdayName => {
+                    WEEKDAY_NAMES[calendar::weekday(days()?) as usize - 1][..3].to_string()
+                }
+                Specifier::Hour => format!("{:02}", time()?.hour),
+                Specifier::Hour12 => format!("{:02}", (time()?.hour + 11) % 12 + 1),
+                Specifier::AmPm => {
+                    if time()?.hour < 12 {
+                        "AM".to_string()
+                    } else {
+                        "PM".to_string()
+                    }
+                }
+                Specifier::Minute => format!("{:02}", time()?.minute),
+                Specifier::Second => format!("{:02}", time()?.second),
+                Specifier::Fraction(digits) => {
+                    let nanos = format!("{:09}", time()?.subsecond_nanos());
+                    nanos[..*digits].to_string()
+                }
+                Specifier::OptionalFraction => {
+                    let nanos = time()?.subsecond_nanos();
+                    if nanos == 0 {
+                        String::new()
+                    } else {
+                        let fraction = format!("{:09}", nanos);
+                        format!(".{}", fraction.trim_end_matches('0'))
+                    }
+                }
+                Specifier::Offset => format_offset(offset()?, false),
+                Specifier::OffsetWithColon => format_offset(offset()?, true),
+                Specifier::Zone => match &time()?.timezone {
+                    TimeZone::Named(name) => name.clone(),
+                    _ => format_offset(offset()?, true),
+                },
+            };
+            result.push_str(&formatted);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Components read by `DateTimeFormat::parse`.
+#[derive(Default)]
+struct Fields {
+    year: Option<i64>,
+    month: Option<i64>,
+    day: Option<i64>,
+    day_of_year: Option<i64>,
+    iso_year: Option<i64>,
+    iso_week: Option<i64>,
+    weekday: Option<i64>,
+    hour: Option<i64>,
+    hour12: Option<i64>,
+    pm: Option<bool>,
+    minute: Option<i64>,
+    second: Option<i64>,
+    nanos: Option<i64>,
+    timezone: Option<TimeZone>,
+}
+
+/// Reads between one and `max_digits` digits.
+fn take_number(input: &mut &str, max_digits: usize) -> Option<i64> {
+    let digits = input
+        .chars()
+        .take(max_digits)
+        .take_while(|c| c.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    let number = input[..digits].parse().ok()?;
+    *input = &input[digits..];
+    Some(number)
+}
+
+/// Reads digits after the decimal point as nanoseconds, truncating digits
+/// beyond nanosecond precision.
+fn take_fraction(input: &mut &str, max_digits: usize) -> Option<i64> {
+    let digits = input.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || digits > max_digits {
+        return None;
+    }
+    let nanos = input[..digits.min(9)]
+        .chars()
+        .chain(std::iter::repeat('0'))
+        .take(9)
+        .collect::<String>()
+        .parse()
+        .ok()?;
+    *input = &input[digits..];
+    Some(nanos)
+}
+
+fn take_signed_year(input: &mut &str) -> Option<i64> {
+    let sign = match input.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return take_number(input, 4),
+    };
+    *input = &input[1..];
+    Some(sign * take_number(input, 9)?)
+}
+
+/// Reads a full or abbreviated name and returns its index in `names`.
+fn take_name(input: &mut &str, names: &[&str]) -> Option<i64> {
+    let starts_with = |input: &str, prefix: &str| {
+        input
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    };
+    for (index, name) in names.iter().enumerate() {
+        for candidate in &[*name, &name[..3]] {
+            if starts_with(input, candidate) {
+                *input = &input[candidate.len()..];
+                return Some(index as i64);
+            }
+        }
+    }
+    None
+}
+
+fn take_offset(input: &mut &str, with_colon: bool) -> Option<TimeZone> {
+    if let Some(rest) = input.strip_prefix('Z') {
+        *input = rest;
+        return Some(TimeZone::Utc);
+    }
+    let length = if with_colon { 6 } else { 5 };
+    let offset = input.get(..length)?;
+    if with_colon && offset.as_bytes()[3] != b':' {
+        return None;
+    }
+    let timezone = offset.parse().ok()?;
+    *input = &input[length..];
+    Some(timezone)
+}
+
+/// Reads a zone name, e.g. `Europe/Paris`, or an offset.
+fn take_zone(input: &mut &str) -> Option<TimeZone> {
+    let length = input
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(input.len());
+    let timezone = input[..length].parse().ok()?;
+    *input = &input[length..];
+    Some(timezone)
+}
+
+/// Matches a literal, where whitespace matches any non-empty run of
+/// whitespace.
+fn take_literal(input: &mut &str, literal: &str) -> Option<()> {
+    for c in literal.chars() {
+        if c.is_whitespace() {
+            let trimmed = input.trim_start();
+            if trimmed.len() == input.len() {
+                return None;
+            }
+            *input = trimmed;
+        } else {
+            *input = input.strip_prefix(c)?;
+        }
+    }
+    Some(())
+}
+
+impl DateTimeFormat {
+    /// Parses a date, time or date time following the pattern. The kind of
+    /// `DateTime` depends on the fields present in the pattern.
+    ///
+    /// Dates need a year, month and day, a year and day of the year, or an
+    /// ISO week-year, week and weekday. Times need at least an hour, and `%I`
+    /// needs `%p`. Weekday names are checked against the date.
+    pub fn parse(&self, s: &str) -> Result<DateTime, ParsingError> {
+        let trimmed = s.trim_start();
+        let leading = s.len() - trimmed.len();
+        let fields = self
+            .read_fields(trimmed.trim_end())
+            .map_err(|(offset, expected)| {
+                ParsingError::cannot_parse(s)
+                    .at_offset(leading + offset)
+                    .with_expected(&expected)
+            })?;
+
+        fields.to_datetime().ok_or_else(|| {
+            let error = ParsingError::cannot_parse(s);
+            if fields.has_date() && fields.to_date().is_none() {
+                error.with_cause(ParsingCause::OutOfRange("date".to_string()))
+            } else if fields.has_time() && fields.to_time().is_none() {
+                error.with_cause(ParsingCause::OutOfRange("time".to_string()))
+            } else {
+                error.with_expected("a date or a time in the pattern")
+            }
+        })
+    }
+
+    /// Reads the fields of the pattern. On failure, returns the offset in
+    /// `s` and a description of the expected item.
+    fn read_fields(&self, s: &str) -> Result<Fields, (usize, String)> {
+        let mut input = s;
+        let mut fields = Fields::default();
+
+        for item in self.items.iter() {
+            let offset = s.len() - input.len();
+            let fail = || (offset, item.describe());
+            let specifier = match item {
+                Item::Literal(literal) => {
+                    take_literal(&mut input, literal).ok_or_else(fail)?;
+                    continue;
+                }
+                Item::Specifier(specifier) => specifier,
+            };
+            if read_field(specifier, &mut input, &mut fields).is_none() {
+                return Err(fail());
+            }
+        }
+
+        if input.is_empty() {
+            Ok(fields)
+        } else {
+            Err((s.len() - input.len(), "the end of the text".to_string()))
+        }
+    }
+}
+
+/// Reads one specifier from the start of `input` into `fields`.
+fn read_field(specifier: &Specifier, input: &mut &str, fields: &mut Fields) -> Option<()> {
+    match specifier {
+        Specifier::Year => fields.year = Some(take_signed_year(input)?),
+        Specifier::ShortYear => {
+            let year = take_number(input, 2)?;
+            fields.year = Some(if year >= 69 { 1900 + year } else { 2000 + year });
+        }
+        Specifier::Month => fields.month = Some(take_number(input, 2)?),
+        Specifier::MonthName | Specifier::ShortMonthName => {
+            fields.month = Some(take_name(input, &MONTH_NAMES)? + 1)
+        }
+        Specifier::Day => fields.day = Some(take_number(input, 2)?),
+        Specifier::DayOfYear => fields.day_of_year = Some(take_number(input, 3)?),
+        Specifier::IsoYear => fields.iso_year = Some(take_signed_year(input)?),
+        Specifier::IsoWeek => fields.iso_week = Some(take_number(input, 2)?),
+        Specifier::Weekday => fields.weekday = Some(take_number(input, 1)?),
+        Specifier::WeekdayName | Specifier::ShortWeekdayName => {
+            fields.weekday = Some(take_name(input, &WEEKDAY_NAMES)? + 1)
+        }
+        Specifier::Hour => fields.hour = Some(take_number(input, 2)?),
+        Specifier::Hour12 => fields.hour12 = Some(take_number(input, 2)?),
+        Specifier::AmPm => {
+            let marker = input.get(..2)?;
+            fields.pm = if marker.eq_ignore_ascii_case("AM") {
+                Some(false)
+            } else if marker.eq_ignore_ascii_case("PM") {
+                Some(true)
+            } else {
+                return None;
+            };
+            *input = &input[2..];
+        }
+        Specifier::Minute => fields.minute = Some(take_number(input, 2)?),
+        Specifier::Second => fields.second = Some(take_number(input, 2)?),
+        Specifier::Fraction(digits) => fields.nanos = Some(take_fraction(input, *digits)?),
+        Specifier::OptionalFraction => {
+            if let Some(rest) = input.strip_prefix(['.', ',']) {
+                *input = rest;
+                fields.nanos = Some(take_fraction(input, usize::MAX)?);
+            }
+        }
+        Specifier::Offset => fields.timezone = Some(take_offset(input, false)?),
+        Specifier::OffsetWithColon => fields.timezone = Some(take_offset(input, true)?),
+        Specifier::Zone => fields.timezone = Some(take_zone(input)?),
+    }
+    Some(())
+}
+
+impl Fields {
+    fn has_date(&self) -> bool {
+        self.year.is_some()
+            || self.month.is_some()
+            || self.day.is_some()
+            || self.day_of_year.is_some()
+            || self.iso_year.is_some()
+            || self.iso_week.is_some()
+    }
+
+    fn has_time(&self) -> bool {
+        self.hour.is_some() || self.hour12.is_some() || self.minute.is_some()
+    }
+
+    fn to_date(&self) -> Option<Date> {
+        let date = match (self.year, self.month, self.day, self.day_of_year) {
+            (Some(year), Some(month), Some(day), None) => Date::YearMonthDay {
+                year: YearNumber::try_from(year).ok()?,
+                month: MonthNumber::try_from(month).ok()?,
+                day: DayNumber::try_from(day).ok()?,
+            },
+            (Some(year), None, None, Some(day_in_year)) => Date::YearDay {
+                year: YearNumber::try_from(year).ok()?,
+                day_in_year: DayOrdinal::try_from(day_in_year).ok()?,
+            },
+            (None, None, None, None) => Date::YearWeekDay {
+                year: YearNumber::try_from(self.iso_year?).ok()?,
+                week_in_year: WeekNumber::try_from(self.iso_week?).ok()?,
+                day_in_week: DayNumber::try_from(self.weekday?).ok()?,
+            },
+            _ => return None,
+        };
+
+        let epoch_days = date.to_epoch_days()?;
+        match self.weekday {
+            Some(weekday) if weekday != calendar::weekday(epoch_days) => None,
+            _ => Some(date),
+        }
+    }
+
+    fn to_time(&self) -> Option<Time> {
+        let hour = match (self.hour, self.hour12, self.pm) {
+            (Some(hour), None, None) => hour,
+            (None, Some(hour @ 1..=12), Some(pm)) => hour % 12 + if pm { 12 } else { 0 },
+            _ => return None,
+        };
+        let nanos = self.nanos.unwrap_or(0);
+        let time = Time {
+            hour: HourNumber::try_from(hour).ok()?,
+            minute: MinuteNumber::try_from(self.minute.unwrap_or(0)).ok()?,
+            second: SecondNumber::try_from(self.second.unwrap_or(0)).ok()?,
+            milli: (nanos / 1_000_000) as MilliNumber,
+            micro: (nanos / 1_000 % 1_000) as MicroNumber,
+            nano: (nanos % 1_000) as NanoNumber,
+            timezone: self.timezone.clone().unwrap_or(TimeZone::Unspecified),
+        };
+
+        if time.is_valid() {
+            Some(time)
+        } else {
+            None
+        }
+    }
+
+    fn to_datetime(&self) -> Option<DateTime> {
+        match (self.has_date(), self.has_time()) {
+            (true, true) => Some(DateTime::Full {
+                date: self.to_date()?,
+                time: self.to_time()?,
+            }),
+            (true, false) => Some(DateTime::Date(self.to_date()?)),
+            (false, true) => Some(DateTime::Time(self.to_time()?)),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Pushes the pending literal, if any, followed by the specifier.
+fn push_specifier(items: &mut Vec<Item>, literal: &mut String, specifier: Specifier) {
+    if !literal.is_empty() {
+        items.push(Item::Literal(std::mem::take(literal)));
+    }
+    items.push(Item::Specifier(specifier));
+}
+
+/// Formats a year with at least four digits. Years outside of 0000-9999 get
+/// a sign, as in the expanded ISO 8601 representation.
+pub(crate) fn format_year(year: i64) -> String {
+    if (0..=9999).contains(&year) {
+        format!("{:04}", year)
+    } else {
+        format!("{:+05}", year)
+    }
+}
+
+fn format_offset(offset_seconds: i64, with_colon: bool) -> String {
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let minutes = offset_seconds.abs() / 60;
+    let separator = if with_colon { ":" } else { "" };
+    format!(
+        "{}{:02}{}{:02}",
+        sign,
+        minutes / 60,
+        separator,
+        minutes % 60
+    )
+}
+
+impl std::str::FromStr for DateTimeFormat {
+    type Err = FormattingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl Date {
+    /// Formats the date with a strftime-style pattern, see `DateTimeFormat`.
+    pub fn format(&self, format: &DateTimeFormat) -> Result<String, FormattingError> {
+        format.format(&DateTime::Date(self.clone()))
+    }
+}
+
+impl DateTime {
+    /// Formats the date time with a strftime-style pattern, see
+    /// `DateTimeFormat`.
+    pub fn format(&self, format: &DateTimeFormat) -> Result<String, FormattingError> {
+        format.format(self)
+    }
+}
+
+impl Time {
+    /// Formats the time with a strftime-style pattern, see `DateTimeFormat`.
+    pub fn format(&self, format: &DateTimeFormat) -> Result<String, FormattingError> {
+        format.format(&DateTime::Time(self.clone()))
+    }
+}
@@ -0,0 +1,69 @@
+//! Offsets of named time zones, looked up in the IANA time zone database
+//! bundled with `chrono-tz`.
+//!
+//! Without the `timezones` feature no zone names are known, and all lookups
+//! return `None`.
+
+#[cfg(feature = "timezones")]
+mod database {
+    use chrono::{Offset, TimeZone};
+    use chrono_tz::Tz;
+
+    fn naive(seconds: i64) -> Option<chrono::NaiveDateTime> {
+        chrono::DateTime::from_timestamp(seconds, 0).map(|dt| dt.naive_utc())
+    }
+
+    pub(crate) fn is_known(name: &str) -> bool {
+        name.parse::<Tz>().is_ok()
+    }
+
+    pub(crate) fn offset_at_utc(name: &str, utc_seconds: i64) -> Option<i64> {
+        let tz = name.parse::<Tz>().ok()?;
+        let offset = tz.offset_from_utc_datetime(&naive(utc_seconds)?);
+        Some(offset.fix().local_minus_utc() as i64)
+    }
+
+    pub(crate) fn offset_at_local(name: &str, local_seconds: i64) -> Option<i64> {
+        let tz = name.parse::<Tz>().ok()?;
+        let offset = tz
+            .offset_from_local_datetime(&naive(local_seconds)?)
+            .earliest()?;
+        Some(offset.fix().local_minus_utc() as i64)
+    }
+}
+
+#[cfg(not(feature = "timezones"))]
+mod database {
+    pub(crate) fn is_known(_name: &str) -> bool {
+        false
+    }
+
+    pub(crate) fn offset_at_utc(_name: &str, _utc_seconds: i64) -> Option<i64> {
+        None
+    }
+
+    pub(crate) fn offset_at_local(_name: &str, _local_seconds: i64) -> Option<i64> {
+        None
+    }
+}
+
+/// Returns true if `name` is a zone in the time zone database, e.g.
+/// `Europe/Luxembourg`.
+pub(crate) fn is_known(name: &str) -> bool {
+    database::is_known(name)
+}
+
+/// Offset from UTC in seconds of the zone `name` at the given instant.
+pub(crate) fn offset_at_utc(name: &str, utc_seconds: i64) -> Option<i64> {
+    database::offset_at_utc(name, utc_seconds)
+}
+
+/// Offset from UTC in seconds of the zone `name` for the given wall clock
+/// time, expressed in seconds since 1970-01-01T00:00:00 local time.
+///
+/// When the clocks are turned back, the wall clock time occurs twice and the
+/// earlier instant (i.e. the larger offset) is used. Wall clock times skipped
+/// when the clocks are turned forward do not exist and yield `None`.
+pub(crate) fn offset_at_local(name: &str, local_seconds: i64) -> Option<i64> {
+    database::offset_at_local(name, local_seconds)
+}
@@ -15,6 +15,16 @@ pub trait CoercesValues {
     fn convert(&self, value: &Value, to_vtype: &ValueType) -> Result<Value, CoercionError>;
 }
 
+/// Allows plain functions and closures as coercion rules.
+impl<F> CoercesValues for F
+where
+    F: Fn(&Value, &ValueType) -> Result<Value, CoercionError>,
+{
+    fn convert(&self, value: &Value, to_vtype: &ValueType) -> Result<Value, CoercionError> {
+        self(value, to_vtype)
+    }
+}
+
 pub trait ParsesValues {
     fn parse(&self, s: &str) -> Result<Value, ParsingError>;
 }
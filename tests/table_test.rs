@@ -95,3 +95,759 @@ mod table {
         }
     }
 }
+
+mod dsv {
+    use datakit::errors::*;
+    use datakit::table::dsv::*;
+    use datakit::table::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::*;
+    use datakit::value::primitives::*;
+
+    fn events_table() -> Table {
+        let schema = Schema::from_tuples(vec![
+            (
+                "Event",
+                ValueContract::new(TypeConstraint::IsType(ValueType::Text), vec![]),
+            ),
+            (
+                "Date",
+                ValueContract::new(TypeConstraint::IsType(ValueType::DateTime), vec![]),
+            ),
+            (
+                "Attendees",
+                ValueContract::new(TypeConstraint::IsType(ValueType::Number), vec![]),
+            ),
+        ]);
+
+        let mut table = Table::from_schema(&schema);
+        table
+            .add_row(&vec![
+                Value::Text("Launch, part 1".into()),
+                DateTime::ymd(2021, 7, 4).into(),
+                12.into(),
+            ])
+            .unwrap();
+        table
+            .add_row(&vec![
+                Value::Text("Retrospective".into()),
+                DateTime::ymd(2021, 7, 18).into(),
+                Value::Missing(Empty::Expected),
+            ])
+            .unwrap();
+        table
+    }
+
+    #[test]
+    fn write_csv() {
+        let csv = DsvWriter::new().write_to_string(&events_table()).unwrap();
+        assert_eq!(
+            csv,
+            "Event,Date,Attendees\n\"Launch, part 1\",2021-07-04,12\nRetrospective,2021-07-18,\n"
+        );
+    }
+
+    #[test]
+    fn write_tsv_with_datetime_format() {
+        let tsv = DsvWriter::new()
+            .with_delimiter(b'\t')
+            .with_headers(false)
+            .with_datetime_format(DateTimeFormat::new("%d/%m/%Y").unwrap())
+            .write_to_string(&events_table())
+            .unwrap();
+        assert_eq!(
+            tsv,
+            "Launch, part 1\t04/07/2021\t12\nRetrospective\t18/07/2021\t\n"
+        );
+    }
+
+    #[test]
+    fn inapplicable_datetime_format() {
+        let result = DsvWriter::new()
+            .with_datetime_format(DateTimeFormat::new("%H:%M").unwrap())
+            .write_to_string(&events_table());
+        match result {
+            Err(DsvError::ValueError { row, column, .. }) => {
+                assert_eq!((row, column.as_str()), (0, "Date"))
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_csv() {
+        let csv =
+            "Event,Date,Attendees\n\"Launch, part 1\",2021-07-04,12\nRetrospective,2021-07-18,\n";
+        let table = DsvReader::new().read_from_str(csv).unwrap();
+        let expected = events_table();
+        assert_eq!(table.columns(), expected.columns());
+        assert_eq!(
+            table.column_contracts()[2].value_contract,
+            expected.column_contracts()[2].value_contract
+        );
+    }
+
+    #[test]
+    fn read_mixed_column_as_text() {
+        let table = DsvReader::new()
+            .with_headers(false)
+            .read_from_str("1\ntwo\n\n")
+            .unwrap();
+        assert_eq!(table.column_contracts()[0].name, "column_1");
+        assert_eq!(
+            table.columns()[0],
+            vec![Value::Text("1".into()), Value::Text("two".into())]
+        );
+    }
+
+    #[test]
+    fn read_durations_only_as_typed() {
+        let csv = "Wait\nP1D\nP999999999999999999Y\n";
+        let table = DsvReader::new().read_from_str(csv).unwrap();
+        assert_eq!(
+            table.columns()[0],
+            vec![
+                Value::Text("P1D".into()),
+                Value::Text("P999999999999999999Y".into())
+            ]
+        );
+
+        let result = DsvReader::new()
+            .with_column_type(ColumnId::Ordinal(0), ValueType::Duration)
+            .read_from_str(csv);
+        match result {
+            Err(DsvError::ParseError { row, error, .. }) => {
+                assert_eq!(row, 1);
+                assert!(matches!(
+                    *error,
+                    ParsingError::CannotParseValue {
+                        cause: Some(ParsingCause::OutOfRange(_)),
+                        ..
+                    }
+                ));
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_with_column_options() {
+        let csv = "Item;Price;Discount;In stock\nTea;1.234,50 €;12%;ja\nCoffee;4,99 €;5 %;nein\n";
+        let german = ParserOptions::new()
+            .with_decimal_separator(',')
+            .with_thousands_separator(Some('.'))
+            .with_currency_symbols(&["€"]);
+        let table = DsvReader::new()
+            .with_delimiter(b';')
+            .with_column_options(ColumnId::Name("Price".into()), german)
+            .with_column_options(
+                ColumnId::Ordinal(2),
+                ParserOptions::new().with_percent(PercentHandling::Fraction),
+            )
+            .with_column_options(
+                ColumnId::Name("In stock".into()),
+                ParserOptions::new()
+                    .with_true_tokens(&["ja"])
+                    .with_false_tokens(&["nein"]),
+            )
+            .read_from_str(csv)
+            .unwrap();
+
+        assert_eq!(
+            table.columns()[1],
+            vec![Value::from(1234.5), Value::from(4.99)]
+        );
+        assert_eq!(
+            table.columns()[2],
+            vec![Value::from(0.12), Value::from(0.05)]
+        );
+        assert_eq!(
+            table.columns()[3],
+            vec![Value::from(true), Value::from(false)]
+        );
+    }
+
+    fn day_or_month_first() -> ParserOptions {
+        ParserOptions::bare().with_datetime_detection(DateTimeDetection::Formats(vec![
+            DateTimeFormat::new("%d/%m/%Y").unwrap(),
+            DateTimeFormat::new("%m/%d/%Y").unwrap(),
+        ]))
+    }
+
+    #[test]
+    fn read_date_columns_as_a_whole() {
+        let csv = "Date,Due\n03/04/2020,03/04/2020\n25/03/2020,\n,04/13/2020\n";
+        let table = DsvReader::new()
+            .with_parser_options(day_or_month_first())
+            .with_column_type(ColumnId::Name("Due".into()), ValueType::DateTime)
+            .read_from_str(csv)
+            .unwrap();
+        assert_eq!(
+            table.column_contracts()[0].value_contract.expected_type,
+            TypeConstraint::IsType(ValueType::DateTime)
+        );
+        assert_eq!(
+            table.columns()[0],
+            vec![
+                Value::from(DateTime::ymd(2020, 4, 3)),
+                Value::from(DateTime::ymd(2020, 3, 25)),
+                Value::Missing(Empty::Expected)
+            ]
+        );
+        assert_eq!(
+            table.columns()[1],
+            vec![
+                Value::from(DateTime::ymd(2020, 3, 4)),
+                Value::Missing(Empty::Expected),
+                Value::from(DateTime::ymd(2020, 4, 13))
+            ]
+        );
+    }
+
+    #[test]
+    fn ambiguous_date_columns_fail() {
+        for column_type in [None, Some(ValueType::DateTime)] {
+            let mut reader = DsvReader::new().with_parser_options(day_or_month_first());
+            if let Some(value_type) = column_type {
+                reader = reader.with_column_type(ColumnId::Ordinal(0), value_type);
+            }
+            match reader.read_from_str("Date\n04/04/2020\n03/04/2020\n") {
+                Err(DsvError::ParseError {
+                    row, line, error, ..
+                }) => {
+                    assert_eq!((row, line), (1, 3));
+                    assert!(matches!(*error, ParsingError::AmbiguousDate { .. }));
+                }
+                other => panic!("Unexpected result {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn read_sentinels_and_text() {
+        let table = DsvReader::new()
+            .read_from_str("Name,Score\nAda,NA\nGrace,-\n\"Lovelace, A.\",7\n")
+            .unwrap();
+        assert_eq!(
+            table.columns()[0],
+            vec![
+                Value::Text("Ada".into()),
+                Value::Text("Grace".into()),
+                Value::Text("Lovelace, A.".into())
+            ]
+        );
+        assert_eq!(
+            table.columns()[1],
+            vec![
+                Value::Missing(Empty::Expected),
+                Value::Missing(Empty::Expected),
+                Value::from(7)
+            ]
+        );
+    }
+
+    #[test]
+    fn read_typed_columns() {
+        let csv = "Code,Date\n2020-123,2021-03-04\n2021W05,\n";
+        let table = DsvReader::new()
+            .with_column_type(ColumnId::Name("Code".into()), ValueType::Text)
+            .with_column_type(ColumnId::Ordinal(1), ValueType::DateTime)
+            .read_from_str(csv)
+            .unwrap();
+        assert_eq!(
+            table.columns()[0],
+            vec![
+                Value::Text("2020-123".into()),
+                Value::Text("2021W05".into())
+            ]
+        );
+        assert_eq!(
+            table.columns()[1],
+            vec![
+                Value::from(DateTime::ymd(2021, 3, 4)),
+                Value::Missing(Empty::Expected)
+            ]
+        );
+    }
+
+    #[test]
+    fn composite_columns_roundtrip() {
+        let schema = Schema::from_tuples(vec![
+            (
+                "Id",
+                ValueContract::new(TypeConstraint::IsType(ValueType::Number), vec![]),
+            ),
+            (
+                "Tags",
+                ValueContract::new(TypeConstraint::IsType(ValueType::Composite), vec![]),
+            ),
+        ]);
+        let tags = Value::Composite(Collection::Object(vec![
+            ("name".into(), Value::Text("a, b".into())),
+            (
+                "scores".into(),
+                Value::Composite(Collection::Array(vec![1.into(), 2.5.into()])),
+            ),
+        ]));
+        let mut table = Table::from_schema(&schema);
+        table.add_row(&vec![1.into(), tags.clone()]).unwrap();
+
+        let csv = DsvWriter::new().write_to_string(&table).unwrap();
+        assert_eq!(
+            csv,
+            "Id,Tags\n1,\"{\"\"name\"\":\"\"a, b\"\",\"\"scores\"\":[1,2.5]}\"\n"
+        );
+
+        let read = DsvReader::new()
+            .with_column_type(ColumnId::Name("Tags".into()), ValueType::Composite)
+            .read_from_str(&csv)
+            .unwrap();
+        assert_eq!(read.columns()[1], vec![tags]);
+
+        let error = DsvReader::new()
+            .with_column_type(ColumnId::Name("Tags".into()), ValueType::Composite)
+            .read_from_str("Id,Tags\n1,\"[1,\"\n")
+            .unwrap_err();
+        assert!(matches!(error, DsvError::ParseError { row: 0, .. }));
+    }
+
+    #[test]
+    fn parse_errors_have_coordinates() {
+        let csv = "Event,Date\nLaunch,2021-03-04\nRetro,2021-02-30\n";
+        let result = DsvReader::new()
+            .with_column_type(ColumnId::Name("Date".into()), ValueType::DateTime)
+            .read_from_str(csv);
+        match result {
+            Err(DsvError::ParseError {
+                row,
+                line,
+                column,
+                error,
+            }) => {
+                assert_eq!((row, line, column.as_str()), (1, 3, "Date"));
+                assert_eq!(
+                    error.to_string(),
+                    "Cannot parse \"2021-02-30\" as DateTime (day out of range)"
+                );
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn errors_are_std_errors() {
+        let read = || -> Result<Table, Box<dyn std::error::Error>> {
+            Ok(DsvReader::new()
+                .with_column_type(ColumnId::Name("Date".into()), ValueType::DateTime)
+                .read_from_str("Date\n2021-02-30\n")?)
+        };
+        assert_eq!(
+            read().unwrap_err().to_string(),
+            "Cannot read column Date of row 0 (line 2): \
+             Cannot parse \"2021-02-30\" as DateTime (day out of range)"
+        );
+    }
+}
+
+mod coercion_rules {
+    use datakit::errors::*;
+    use datakit::table::*;
+    use datakit::value::coercion::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+
+    fn yes_no(value: &Value, _: &ValueType) -> Result<Value, CoercionError> {
+        match value {
+            Value::Text(s) if s == "Y" => Ok(Value::from(true)),
+            Value::Text(s) if s == "N" => Ok(Value::from(false)),
+            _ => Err(CoercionError::UnexpectedType),
+        }
+    }
+
+    fn schema() -> Schema {
+        let mut schema = Schema::from_tuples(vec![
+            (
+                "active",
+                ValueContract::new(TypeConstraint::IsType(ValueType::Boolean), vec![]),
+            ),
+            (
+                "count",
+                ValueContract::new(TypeConstraint::IsType(ValueType::Number), vec![]),
+            ),
+        ]);
+        schema.column_contracts[0].coercion_rule = Some("yesNo".into());
+        schema
+    }
+
+    #[test]
+    fn schemas_reference_rules_by_name() {
+        let json = serde_json::to_value(schema()).unwrap();
+        assert_eq!(json["columnContracts"][0]["coercionRule"], "yesNo");
+        assert!(json["columnContracts"][1].get("coercionRule").is_none());
+
+        let schema: Schema = serde_json::from_value(json).unwrap();
+        let registry = CoercionRegistry::new().with_named_rule("yesNo", yes_no);
+        let active = &schema.column_contracts[0];
+        assert_eq!(
+            active.coerce(&Value::Text("N".into()), &registry).unwrap(),
+            Value::from(false)
+        );
+        let count = &schema.column_contracts[1];
+        assert_eq!(
+            count.coerce(&Value::Text("7".into()), &registry).unwrap(),
+            Value::from(7)
+        );
+    }
+
+    #[test]
+    fn unknown_rules_fail() {
+        let schema = schema();
+        assert!(matches!(
+            schema.column_contracts[0].coerce(&Value::Text("Y".into()), &CoercionRegistry::new()),
+            Err(CoercionError::UnknownRule(_))
+        ));
+    }
+}
+
+mod schema_coercion {
+    use datakit::errors::*;
+    use datakit::table::*;
+    use datakit::value::coercion::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+
+    fn text(s: &str) -> Value {
+        Value::Text(s.into())
+    }
+
+    fn contract(value_type: ValueType) -> ValueContract {
+        ValueContract::new(TypeConstraint::IsType(value_type), vec![])
+    }
+
+    fn raw_table() -> Table {
+        let schema = Schema::from_tuples(vec![
+            ("id", contract(ValueType::Text)),
+            ("amount", contract(ValueType::Text)),
+            ("paid", contract(ValueType::Text)),
+        ]);
+        let mut table = Table::from_schema(&schema);
+        table
+            .add_row(&vec![text("a"), text(" 12.5 "), text("true")])
+            .unwrap();
+        table
+            .add_row(&vec![
+                text("b"),
+                text("n/a"),
+                Value::Missing(Empty::Expected),
+            ])
+            .unwrap();
+        table
+            .add_row(&vec![text("c"), text("3"), text("maybe")])
+            .unwrap();
+        table
+    }
+
+    fn schema() -> Schema {
+        Schema::from_tuples(vec![
+            ("amount", contract(ValueType::Number)),
+            ("paid", contract(ValueType::Boolean)),
+        ])
+    }
+
+    #[test]
+    fn failed_cells_become_missing() {
+        let (table, report) = raw_table()
+            .coerce_to(&schema(), &TableCoercion::new())
+            .unwrap();
+        assert_eq!(
+            table.columns()[1],
+            vec![
+                Value::from(12.5),
+                Value::Missing(Empty::Unexpected),
+                Value::from(3)
+            ]
+        );
+        assert_eq!(
+            table.columns()[2],
+            vec![
+                Value::from(true),
+                Value::Missing(Empty::Expected),
+                Value::Missing(Empty::Unexpected)
+            ]
+        );
+        assert_eq!(table.columns()[0], raw_table().columns()[0]);
+        assert_eq!(
+            table.column_contracts()[1].value_contract,
+            contract(ValueType::Number)
+        );
+
+        let failures: Vec<_> = report
+            .failures
+            .iter()
+            .map(|failure| (failure.row, failure.column.as_str()))
+            .collect();
+        assert_eq!(failures, vec![(1, "amount"), (2, "paid")]);
+        assert!(matches!(
+            report.failures[0].error,
+            CoercionError::CoercionFailed { .. }
+        ));
+
+        assert_eq!(report.losses.len(), 1);
+        assert_eq!(
+            (report.losses[0].row, report.losses[0].column.as_str()),
+            (0, "amount")
+        );
+        assert_eq!(report.losses[0].loss.kind, LossKind::TrimmedWhitespace);
+    }
+
+    #[test]
+    fn failed_cells_can_stay_unchanged() {
+        let coercion = TableCoercion::new().with_failed_cells(FailedCells::Unchanged);
+        let (table, report) = raw_table().coerce_to(&schema(), &coercion).unwrap();
+        assert_eq!(table.columns()[1][1], text("n/a"));
+        assert_eq!(table.columns()[2][2], text("maybe"));
+        assert_eq!(report.failures.len(), 2);
+    }
+
+    #[test]
+    fn policies_and_rules_apply() {
+        let strict = CoercionRegistry::new()
+            .with_fallback(Coercion::new().with_policy(CoercionPolicy::Strict));
+        let (_, report) = raw_table()
+            .coerce_to(&schema(), &TableCoercion::new().with_registry(strict))
+            .unwrap();
+        assert!(matches!(
+            report.failures[0].error,
+            CoercionError::InformationLoss(_)
+        ));
+        assert!(report.losses.is_empty());
+
+        let mut schema = schema();
+        schema.column_contracts[1].coercion_rule = Some("maybe".into());
+        let registry =
+            CoercionRegistry::new().with_named_rule("maybe", |value: &Value, _: &ValueType| {
+                match value {
+                    Value::Text(s) => Ok(Value::from(s == "true")),
+                    _ => Err(CoercionError::UnexpectedType),
+                }
+            });
+        let (table, report) = raw_table()
+            .coerce_to(&schema, &TableCoercion::new().with_registry(registry))
+            .unwrap();
+        assert_eq!(table.columns()[2][2], Value::from(false));
+        assert_eq!(report.failures.len(), 1);
+    }
+
+    #[test]
+    fn unknown_columns_fail() {
+        let schema = Schema::from_tuples(vec![("other", contract(ValueType::Number))]);
+        assert!(matches!(
+            raw_table().coerce_to(&schema, &TableCoercion::new()),
+            Err(TableError::ColumnError(ColumnError::Unknown(_)))
+        ));
+    }
+}
+
+mod structured_columns {
+    use datakit::table::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use serde_json::json;
+
+    fn array(items: Vec<Value>) -> Value {
+        Value::Composite(Collection::Array(items))
+    }
+
+    fn numbers() -> TypeConstraint {
+        TypeConstraint::ArrayOf(Box::new(TypeConstraint::IsType(ValueType::Number)))
+    }
+
+    fn table(values: Vec<Value>) -> Table {
+        let schema = Schema::from_tuples(vec![(
+            "scores",
+            ValueContract::new(TypeConstraint::IsType(ValueType::Composite), vec![]),
+        )]);
+        let mut table = Table::from_schema(&schema);
+        for value in values {
+            table.add_row(&vec![value]).unwrap();
+        }
+        table
+    }
+
+    #[test]
+    fn infer_schema() {
+        let table = table(vec![
+            array(vec![1.into(), 2.into()]),
+            Value::Missing(Empty::Expected),
+            array(vec![]),
+        ]);
+        let schema = table.infer_schema();
+        assert_eq!(
+            schema.column_contracts[0].value_contract.expected_type,
+            numbers()
+        );
+        assert_eq!(
+            schema.to_json_schema(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "scores": {"type": "array", "items": {"type": "number"}}
+                },
+                "required": ["scores"]
+            })
+        );
+    }
+
+    #[test]
+    fn coerce_array_items() {
+        let table = table(vec![
+            Value::Text("[1, \"2\"]".into()),
+            array(vec!["x".into()]),
+        ]);
+        let schema = Schema::from_tuples(vec![("scores", ValueContract::new(numbers(), vec![]))]);
+        let (coerced, report) = table.coerce_to(&schema, &TableCoercion::new()).unwrap();
+        assert_eq!(
+            coerced.columns()[0],
+            vec![
+                array(vec![1.into(), 2.into()]),
+                Value::Missing(Empty::Unexpected)
+            ]
+        );
+        assert_eq!(report.failures.len(), 1);
+        coerced.validate_column(&ColumnId::Ordinal(0)).unwrap_err();
+
+        let mut valid = coerced.clone();
+        valid
+            .map_column(&ColumnId::Ordinal(0), |value| match value {
+                Value::Missing(_) => array(vec![]),
+                value => value.clone(),
+            })
+            .unwrap();
+        valid.validate_column(&ColumnId::Ordinal(0)).unwrap();
+    }
+}
+
+mod nested_columns {
+    use datakit::table::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+
+    fn object(entries: Vec<(&str, Value)>) -> Value {
+        Value::Composite(Collection::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        ))
+    }
+
+    fn address(city: &str) -> Value {
+        object(vec![("city", city.into())])
+    }
+
+    fn city() -> ColumnId {
+        ColumnId::Name("address".into()).nested("city".parse().unwrap())
+    }
+
+    fn table() -> Table {
+        let schema = Schema::from_tuples(vec![(
+            "address",
+            ValueContract::new(TypeConstraint::IsType(ValueType::Composite), vec![]),
+        )]);
+        let mut table = Table::from_schema(&schema);
+        table.add_row(&vec![address("london")]).unwrap();
+        table.add_row(&vec![object(vec![])]).unwrap();
+        table.add_row(&vec!["London".into()]).unwrap();
+        table
+    }
+
+    #[test]
+    fn map_nested_values() {
+        let mut table = table();
+        table
+            .map_column(&city(), |value| match value {
+                Value::Text(text) => Value::Text(text.to_uppercase()),
+                value => value.clone(),
+            })
+            .unwrap();
+        assert_eq!(
+            table.columns()[0],
+            vec![address("LONDON"), object(vec![]), "London".into()]
+        );
+    }
+
+    #[test]
+    fn map_nested_values_if() {
+        let mut table = table();
+        table.add_row(&vec![address("paris")]).unwrap();
+        let predicates = vec![(city(), |value: &Value| *value != Value::from("london"))];
+        table
+            .map_column_if(
+                &city(),
+                |value| match value {
+                    Value::Text(text) => Value::Text(text.to_uppercase()),
+                    value => value.clone(),
+                },
+                &predicates,
+            )
+            .unwrap();
+        assert_eq!(
+            table.columns()[0],
+            vec![
+                address("london"),
+                object(vec![]),
+                "London".into(),
+                address("PARIS")
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_nested_values() {
+        let table = table();
+        let contract = ColumnContract {
+            name: "city".into(),
+            value_contract: ValueContract::new(
+                TypeConstraint::IsType(ValueType::Text),
+                vec![ValueConstraint::MinimumLength(1)],
+            ),
+            coercion_rule: None,
+        };
+        match table.validate_column_against_contract(&city(), &contract) {
+            Err(TableError::ColumnError(ColumnError::ContainsInvalidValues { errors, .. })) => {
+                assert_eq!(
+                    errors.iter().map(|(row, _)| *row).collect::<Vec<_>>(),
+                    vec![1, 2]
+                );
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        // Other methods act on the containing column.
+        match table.validate_column(&city()) {
+            Err(TableError::ColumnError(ColumnError::ContainsInvalidValues {
+                contract,
+                errors,
+            })) => {
+                assert_eq!(contract.name, "address");
+                assert_eq!(errors.len(), 1);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn unknown_column() {
+        let mut table = table();
+        let id = ColumnId::Name("home".into()).nested("city".parse().unwrap());
+        assert!(matches!(
+            table.map_column(&id, Value::clone),
+            Err(TableError::ColumnError(ColumnError::Unknown(
+                ColumnId::Name(_)
+            )))
+        ));
+    }
+}
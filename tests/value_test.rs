@@ -1,3 +1,73 @@
+/// Values and helpers shared by the test modules below.
+mod fixtures {
+    use datakit::errors::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use std::str::FromStr;
+
+    /// Parses `literal` as a JSON string.
+    pub fn parse(literal: &str) -> Value {
+        Parser::new().parse(&format!("\"{}\"", literal)).unwrap()
+    }
+
+    pub fn decimal(s: &str) -> Value {
+        Value::from(BigDecimal::from_str(s).unwrap())
+    }
+
+    pub fn bigint(s: &str) -> BigInt {
+        BigInt::from_str(s).unwrap()
+    }
+
+    pub fn ymd(year: i32, month: u8, day: u8) -> Date {
+        Date::YearMonthDay { year, month, day }
+    }
+
+    pub fn full(date: Date, hour: u8, minute: u8, timezone: TimeZone) -> DateTime {
+        DateTime::Full {
+            date,
+            time: Time {
+                hour,
+                minute,
+                second: 0,
+                milli: 0,
+                micro: 0,
+                nano: 0,
+                timezone,
+            },
+        }
+    }
+
+    pub fn array(items: Vec<Value>) -> Value {
+        Value::Composite(Collection::Array(items))
+    }
+
+    pub fn object(entries: Vec<(&str, Value)>) -> Value {
+        Value::Composite(Collection::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        ))
+    }
+
+    /// The errors of validating `value`, or none if it is valid.
+    pub fn failed_constraints(
+        validator: &impl ValidatesValues,
+        value: &Value,
+    ) -> Vec<ConstraintError> {
+        match validator.validate(value) {
+            Ok(()) => vec![],
+            Err(ValidationError::ValueValidationError {
+                failed_constraints, ..
+            }) => failed_constraints,
+        }
+    }
+}
+
+// The baseline `f64` conversion test uses 3.14.
+#[allow(clippy::approx_constant)]
 mod common_traits {
     use datakit::value::definitions::*;
     use datakit::value::primitives::*;
@@ -142,3 +212,2916 @@ pub mod value_parsing {
         parser.parse(bad_literal).unwrap_err();
     }
 }
+
+mod arithmetic {
+    use datakit::errors::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+
+    #[test]
+    fn integer_operations_stay_integer() {
+        let a = Value::from(7);
+        let b = Value::from(2);
+        assert_eq!((&a + &b).unwrap(), Value::from(9));
+        assert_eq!((&a - &b).unwrap(), Value::from(5));
+        assert_eq!((&a * &b).unwrap(), Value::from(14));
+        assert_eq!((&a % &b).unwrap(), Value::from(1));
+        assert_eq!((-a).unwrap(), Value::from(-7));
+    }
+
+    #[test]
+    fn inexact_integer_division_promotes_to_real() {
+        assert_eq!((Value::from(8) / Value::from(2)).unwrap(), Value::from(4));
+        assert_eq!((Value::from(7) / Value::from(2)).unwrap(), Value::from(3.5));
+    }
+
+    #[test]
+    fn mixed_operands_are_promoted() {
+        let real = (Value::from(2) + Value::from(0.5)).unwrap();
+        assert_eq!(real, Value::Number(Numeric::Real(2.5)));
+
+        let complex = (Value::from(2) * Value::from((1.0, 1.0))).unwrap();
+        assert_eq!(complex, Value::Number(Numeric::Complex(2.0, 2.0)));
+    }
+
+    #[test]
+    fn complex_division() {
+        let a = Numeric::Complex(3.0, 4.0);
+        let b = Numeric::Complex(1.0, 2.0);
+        assert_eq!((a / b).unwrap(), Numeric::Complex(2.2, -0.4));
+    }
+
+    #[test]
+    fn missing_operands_propagate() {
+        let expected = Value::Missing(Empty::Expected);
+        let unexpected = Value::Missing(Empty::Unexpected);
+        assert_eq!((&expected + &Value::from(1)).unwrap(), expected);
+        assert_eq!((&expected * &unexpected).unwrap(), unexpected);
+        assert_eq!((-expected.clone()).unwrap(), expected);
+    }
+
+    #[test]
+    fn integer_overflow_is_an_error() {
+        let result = Value::from(i64::MAX) + Value::from(1);
+        assert!(matches!(result, Err(ArithmeticError::Overflow(_))));
+
+        let result = -Value::from(i64::MIN);
+        assert!(matches!(result, Err(ArithmeticError::Overflow(_))));
+    }
+
+    #[test]
+    fn integer_division_by_zero_is_an_error() {
+        let result = Value::from(1) / Value::from(0);
+        assert!(matches!(result, Err(ArithmeticError::DivisionByZero)));
+    }
+
+    #[test]
+    fn invalid_operands_are_an_error() {
+        let result = Value::from("abc") + Value::from(true);
+        match result {
+            Err(ArithmeticError::InvalidOperands { left, right, .. }) => {
+                assert_eq!(left, ValueType::Text);
+                assert_eq!(right, ValueType::Boolean);
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn numeric_comparison_across_subtypes() {
+        assert!(Value::from(2) < Value::from(2.5));
+        assert!(Value::from(3.0) > Value::from(2));
+        assert!(Numeric::Complex(1.0, 0.0) < Numeric::Integer(2));
+        assert_eq!(
+            Numeric::Complex(1.0, 1.0).partial_cmp(&Numeric::Integer(2)),
+            None
+        );
+    }
+
+    #[test]
+    fn numeric_equality_across_subtypes() {
+        assert_eq!(Value::from(2), Value::from(2.0));
+        assert_eq!(
+            Value::from(2).partial_cmp(&Value::from(2.0)),
+            Some(std::cmp::Ordering::Equal)
+        );
+        assert_eq!(Numeric::Complex(2.0, 0.0), Numeric::Integer(2));
+        assert_ne!(Value::from(2), Value::from(2.5));
+        assert_ne!(Numeric::Complex(2.0, 1.0), Numeric::Integer(2));
+        assert_ne!(Numeric::Real(f64::NAN), Numeric::Real(f64::NAN));
+    }
+}
+
+mod decimals {
+    use datakit::value::coercion::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use std::str::FromStr;
+
+    use crate::fixtures::*;
+
+    #[test]
+    fn exact_decimal_literals() {
+        let parser = Parser::new().with_exact_decimals(true);
+        let value = parser.parse("0.1000000000000000000000001").unwrap();
+        assert_eq!(value, decimal("0.1000000000000000000000001"));
+
+        let integer = parser.parse("42").unwrap();
+        assert_eq!(integer, Value::from(42));
+    }
+
+    #[cfg(feature = "exact_decimals")]
+    #[test]
+    fn exact_decimals_in_composites() {
+        let parser = Parser::new().with_exact_decimals(true);
+        let value = parser
+            .parse("[0.1000000000000000000000001, 123456789012345678901234567890]")
+            .unwrap();
+        assert_eq!(
+            value,
+            Value::Composite(Collection::Array(vec![
+                decimal("0.1000000000000000000000001"),
+                Value::Number(Numeric::BigInteger(
+                    BigInt::from_str("123456789012345678901234567890").unwrap()
+                )),
+            ]))
+        );
+    }
+
+    #[test]
+    fn composites_are_written_exactly() {
+        let value = Value::Composite(Collection::Array(vec![
+            decimal("0.1000000000000000000000001"),
+            Value::Number(Numeric::BigInteger(
+                BigInt::from_str("123456789012345678901234567890").unwrap(),
+            )),
+        ]));
+        assert_eq!(
+            Coercion::new().convert(&value, &ValueType::Text).unwrap(),
+            Value::Text("[0.1000000000000000000000001,123456789012345678901234567890]".into())
+        );
+    }
+
+    #[test]
+    fn decimal_arithmetic_is_exact() {
+        let sum = (decimal("0.1") + decimal("0.2")).unwrap();
+        assert_eq!(sum, decimal("0.3"));
+
+        let total = (decimal("19.99") * Value::from(3)).unwrap();
+        assert_eq!(total, decimal("59.97"));
+    }
+
+    #[test]
+    fn decimal_constraints() {
+        let price = decimal("10.05");
+        ValueConstraint::Minimum(decimal("10"))
+            .validate(&price)
+            .unwrap();
+        ValueConstraint::Maximum(Value::from(11))
+            .validate(&price)
+            .unwrap();
+        ValueConstraint::MultipleOf(decimal("0.05"))
+            .validate(&price)
+            .unwrap();
+        ValueConstraint::MultipleOf(decimal("0.1"))
+            .validate(&price)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn decimal_coercion() {
+        let coercion = Coercion::new();
+        let text = coercion
+            .convert(&decimal("1.50"), &ValueType::Text)
+            .unwrap();
+        assert_eq!(text, Value::Text("1.50".into()));
+
+        let from_text = coercion
+            .convert_numeric(&text, &NumericType::Decimal)
+            .unwrap();
+        assert_eq!(from_text, decimal("1.5"));
+
+        let from_real = coercion
+            .convert_numeric(&Value::from(0.1), &NumericType::Decimal)
+            .unwrap();
+        assert_eq!(from_real, decimal("0.1"));
+
+        let to_real = coercion
+            .convert_numeric(&decimal("0.25"), &NumericType::Real)
+            .unwrap();
+        assert_eq!(to_real, Value::from(0.25));
+    }
+
+    #[test]
+    fn decimal_serialization_is_lossless() {
+        let value = decimal("12345678901234567890.123456789");
+        let json = serde_json::to_string(&value).unwrap();
+        let roundtrip: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip, value);
+    }
+}
+
+mod big_integers {
+    use datakit::value::coercion::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    use crate::fixtures::*;
+
+    #[test]
+    fn literals_above_i64_max_are_exact() {
+        let parser = Parser::new();
+        let unsigned = parser.parse("18446744073709551615").unwrap();
+        assert_eq!(unsigned, Value::Number(Numeric::Unsigned(u64::MAX)));
+
+        let hash = "340282366920938463463374607431768211455";
+        let big = parser.parse(hash).unwrap();
+        assert_eq!(big, Value::Number(Numeric::BigInteger(bigint(hash))));
+
+        let negative = parser.parse("-9223372036854775809").unwrap();
+        assert_eq!(
+            negative,
+            Value::Number(Numeric::BigInteger(bigint("-9223372036854775809")))
+        );
+    }
+
+    #[test]
+    fn from_and_option_conversions() {
+        assert_eq!(Value::from(7u32), Value::Number(Numeric::Integer(7)));
+        assert_eq!(
+            Value::from(u64::MAX),
+            Value::Number(Numeric::Unsigned(u64::MAX))
+        );
+        assert_eq!(Value::from(5i128), Value::Number(Numeric::Integer(5)));
+        assert_eq!(
+            Value::from(u128::MAX),
+            Value::Number(Numeric::BigInteger(BigInt::from(u128::MAX)))
+        );
+
+        let x: Option<u64> = Value::from(u64::MAX).into();
+        assert_eq!(x, Some(u64::MAX));
+        let y: Option<u8> = Value::from(300).into();
+        assert_eq!(y, None);
+        let z: Option<u128> = Value::from(u128::MAX).into();
+        assert_eq!(z, Some(u128::MAX));
+    }
+
+    #[test]
+    fn mixed_integer_arithmetic_is_exact() {
+        let sum = (Value::from(u64::MAX) + Value::from(1)).unwrap();
+        assert_eq!(
+            sum,
+            Value::Number(Numeric::BigInteger(bigint("18446744073709551616")))
+        );
+
+        let difference = (sum - Value::from(u64::MAX)).unwrap();
+        assert_eq!(difference, Value::from(1));
+        assert!(Value::from(u64::MAX) > Value::from(i64::MAX));
+    }
+
+    fn numeric_type(value: &Value) -> Option<NumericType> {
+        match value {
+            Value::Number(n) => Some(n.numeric_type()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn unsigned_only_above_i64_max() {
+        assert_eq!(numeric_type(&Value::from(5u64)), Some(NumericType::Integer));
+        assert_eq!(
+            numeric_type(&Value::from(5usize)),
+            Some(NumericType::Integer)
+        );
+        assert_eq!(
+            numeric_type(&Value::from(u64::MAX)),
+            Some(NumericType::Unsigned)
+        );
+        assert_eq!(
+            numeric_type(&Value::from(BigInt::from(5))),
+            Some(NumericType::Integer)
+        );
+        assert_eq!(
+            numeric_type(&Value::from(BigInt::from(u64::MAX))),
+            Some(NumericType::Unsigned)
+        );
+        assert_eq!(
+            numeric_type(&Value::from(bigint("18446744073709551616"))),
+            Some(NumericType::BigInteger)
+        );
+
+        let small = Numeric::Unsigned(5) - Numeric::Unsigned(10);
+        assert!(matches!(small, Ok(Numeric::Integer(-5))));
+        let large = Numeric::Unsigned(u64::MAX) + Numeric::Unsigned(u64::MAX);
+        assert_eq!(large.unwrap().numeric_type(), NumericType::BigInteger);
+        let quotient = Numeric::Unsigned(u64::MAX) / Numeric::Unsigned(u64::MAX);
+        assert!(matches!(quotient, Ok(Numeric::Integer(1))));
+    }
+
+    #[test]
+    fn big_integer_coercion_and_serialization() {
+        let coercion = Coercion::new();
+        let value = Value::Number(Numeric::BigInteger(bigint(
+            "123456789012345678901234567890",
+        )));
+
+        let text = coercion.convert(&value, &ValueType::Text).unwrap();
+        assert_eq!(text, Value::Text("123456789012345678901234567890".into()));
+        let back = coercion
+            .convert_numeric(&text, &NumericType::BigInteger)
+            .unwrap();
+        assert_eq!(back, value);
+
+        let json = serde_json::to_string(&value).unwrap();
+        let roundtrip: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip, value);
+    }
+}
+
+mod complex_numbers {
+    use datakit::value::coercion::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    macro_rules! test_complex_parsing {
+        ( $($desc:ident : $lit:expr => ($re:expr, $im:expr)),+ ) => {
+            $(
+                #[test]
+                fn $desc() {
+                    let parser = Parser::new();
+                    let value = parser.parse($lit).unwrap();
+                    assert_eq!(value, Value::Number(Numeric::Complex($re, $im)));
+                }
+            )+
+        };
+    }
+
+    test_complex_parsing! {
+        complex_with_i : "3+4i" => (3.0, 4.0),
+        complex_with_j : "3-4j" => (3.0, -4.0),
+        complex_tuple : "(3, 4)" => (3.0, 4.0),
+        complex_imaginary_only : "-2.5i" => (0.0, -2.5),
+        complex_unit_coefficient : "1-i" => (1.0, -1.0),
+        complex_with_exponents : "1e-3+2E2i" => (0.001, 200.0)
+    }
+
+    #[test]
+    fn invalid_complex_literals_are_rejected() {
+        let parser = Parser::new();
+        parser.parse("i").unwrap_err();
+        parser.parse("3+4k").unwrap_err();
+        parser.parse("(3,4,5)").unwrap_err();
+        parser.parse("inf+infi").unwrap_err();
+    }
+
+    #[test]
+    fn complex_text_roundtrip() {
+        let coercion = Coercion::new();
+        let value = Value::Number(Numeric::Complex(1.5, -2.0));
+        let text = coercion.convert(&value, &ValueType::Text).unwrap();
+        assert_eq!(text, Value::Text("1.5-2i".into()));
+
+        let back = coercion.convert(&text, &ValueType::Number).unwrap();
+        assert_eq!(back, value);
+    }
+
+    #[test]
+    fn bounds_reject_complex_values() {
+        let value = Value::Number(Numeric::Complex(1.0, 1.0));
+        ValueConstraint::Maximum(10.into())
+            .validate(&value)
+            .unwrap_err();
+
+        let real_valued = Value::Number(Numeric::Complex(1.0, 0.0));
+        ValueConstraint::Maximum(10.into())
+            .validate(&real_valued)
+            .unwrap();
+    }
+}
+
+mod dates {
+    use datakit::errors::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    use crate::fixtures::*;
+
+    fn ywd(year: i32, week_in_year: u8, day_in_week: u8) -> Date {
+        Date::YearWeekDay {
+            year,
+            week_in_year,
+            day_in_week,
+        }
+    }
+
+    #[test]
+    fn calendar_validity() {
+        assert!(ymd(2020, 2, 29).is_valid());
+        assert!(!ymd(2021, 2, 29).is_valid());
+        assert!(!ymd(1900, 2, 29).is_valid());
+        assert!(ymd(2000, 2, 29).is_valid());
+        assert!(!ymd(2021, 13, 1).is_valid());
+        assert!(!ymd(2021, 4, 31).is_valid());
+
+        assert!(Date::YearDay {
+            year: 2020,
+            day_in_year: 366
+        }
+        .is_valid());
+        assert!(!Date::YearDay {
+            year: 2021,
+            day_in_year: 366
+        }
+        .is_valid());
+
+        assert!(ywd(2020, 53, 7).is_valid());
+        assert!(!ywd(2021, 53, 1).is_valid());
+        assert!(!ywd(2021, 54, 1).is_valid());
+        assert!(!ywd(2021, 10, 8).is_valid());
+    }
+
+    #[test]
+    fn representation_conversions() {
+        // 2019-12-30 is the Monday of the first ISO week of 2020.
+        let date = ymd(2019, 12, 30);
+        assert_eq!(date.to_year_week_day(), Some(ywd(2020, 1, 1)));
+        assert_eq!(
+            date.to_year_day(),
+            Some(Date::YearDay {
+                year: 2019,
+                day_in_year: 364
+            })
+        );
+        assert_eq!(ywd(2021, 1, 1).to_year_month_day(), Some(ymd(2021, 1, 4)));
+        assert_eq!(ymd(2021, 13, 1).to_year_week_day(), None);
+    }
+
+    #[test]
+    fn equivalent_dates_compare_equal() {
+        let ordinal = Date::YearDay {
+            year: 2020,
+            day_in_year: 60,
+        };
+        assert_eq!(ordinal, ymd(2020, 2, 29));
+        assert_eq!(ywd(2020, 53, 5), ymd(2021, 1, 1));
+        assert!(ywd(2020, 1, 1) < ymd(2020, 1, 1));
+    }
+
+    #[test]
+    fn epoch_days_at_the_extremes() {
+        for days in [i64::MIN, i64::MIN + 719_468, i64::MAX - 719_468, i64::MAX].iter() {
+            assert_eq!(Date::from_epoch_days(*days), None);
+        }
+        let day_nanos = 86_400 * 1_000_000_000;
+        assert_eq!(
+            DateTime::from_epoch_nanos(i64::MAX as i128 * day_nanos),
+            None
+        );
+        assert_eq!(
+            DateTime::from_epoch_nanos(i64::MIN as i128 * day_nanos),
+            None
+        );
+
+        let last = Date::YearMonthDay {
+            year: i32::MAX,
+            month: 12,
+            day: 31,
+        };
+        let days = last.to_epoch_days().unwrap();
+        assert_eq!(Date::from_epoch_days(days), Some(last));
+        assert_eq!(Date::from_epoch_days(days + 1), None);
+    }
+
+    #[test]
+    fn week_dates_are_checked_against_the_week_year() {
+        let parser = Parser::new();
+        for literal in ["\"2020-W53-1\"", "\"2020W531\""].iter() {
+            let value = parser.parse(literal).unwrap();
+            assert_eq!(value, Value::DateTime(DateTime::Date(ywd(2020, 53, 1))));
+            assert_eq!(value, Value::DateTime(DateTime::ymd(2020, 12, 28)));
+        }
+        assert_eq!(
+            parser
+                .parse("\"2020-W53-1T10:00\"")
+                .unwrap()
+                .get_value_type(),
+            &ValueType::DateTime
+        );
+
+        for literal in ["2021-W53-1", "2020-W54-1"].iter() {
+            let quoted = format!("\"{}\"", literal);
+            assert_eq!(
+                parser.parse(&quoted).unwrap(),
+                Value::Text(literal.to_string())
+            );
+            match parser.parse_as(&quoted, &ValueType::DateTime) {
+                Err(ParsingError::CannotParseValue { cause, .. }) => {
+                    assert_eq!(cause, Some(ParsingCause::OutOfRange("week".to_string())))
+                }
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_dates_are_not_parsed_as_dates() {
+        let parser = Parser::new();
+        let value = parser.parse("\"2021-02-29\"").unwrap();
+        assert_eq!(value, Value::Text("2021-02-29".into()));
+
+        let value = parser.parse("\"2020-02-29\"").unwrap();
+        assert_eq!(value, Value::DateTime(DateTime::ymd(2020, 2, 29)));
+    }
+}
+
+mod durations {
+    use datakit::errors::*;
+    use datakit::value::arithmetic::ArithmeticOperation;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use std::str::FromStr;
+
+    use crate::fixtures::*;
+
+    #[test]
+    fn iso8601_duration_parsing() {
+        let duration = Duration::from_str("P3DT4H").unwrap();
+        assert_eq!(duration, Duration::new(0, 3, 4 * 3600, 0));
+
+        let duration = Duration::from_str("P1Y2M1W").unwrap();
+        assert_eq!(duration, Duration::new(14, 7, 0, 0));
+
+        let duration = Duration::from_str("-PT1.5S").unwrap();
+        assert_eq!(duration, Duration::new(0, 0, -1, -500_000_000));
+
+        Duration::from_str("P").unwrap_err();
+        Duration::from_str("P1H").unwrap_err();
+        Duration::from_str("PT1M1H").unwrap_err();
+        Duration::from_str("P1.5D").unwrap_err();
+    }
+
+    #[test]
+    fn overflowing_components_are_out_of_range() {
+        let cases = [
+            ("P999999999999999999Y", "years"),
+            ("P1W9223372036854775807D", "days"),
+            ("P99999999999999999999D", "days"),
+            ("PT9223372036854775807H", "seconds"),
+        ];
+        for (literal, component) in cases.iter() {
+            match Duration::from_str(literal) {
+                Err(ParsingError::CannotParseValue { cause, .. }) => assert_eq!(
+                    cause,
+                    Some(ParsingCause::OutOfRange(component.to_string())),
+                    "{}",
+                    literal
+                ),
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+
+        let value = Parser::new().parse("\"P999999999999999999Y\"").unwrap();
+        assert_eq!(value, Value::Text("P999999999999999999Y".into()));
+    }
+
+    #[test]
+    fn duration_text_roundtrip() {
+        for literal in &["P1Y2M3DT4H5M6.25S", "-P10D", "PT0S", "PT36H"] {
+            let duration = Duration::from_str(literal).unwrap();
+            assert_eq!(duration.to_string(), *literal);
+        }
+    }
+
+    #[test]
+    fn durations_are_only_parsed_when_requested() {
+        let parser = Parser::new();
+        let value = parser.parse("\"P30D\"").unwrap();
+        assert_eq!(value, Value::Text("P30D".into()));
+        let value = parser.parse_as("\"P30D\"", &ValueType::Duration).unwrap();
+        assert_eq!(value, Value::Duration(Duration::days(30)));
+    }
+
+    #[test]
+    fn adding_months_respects_month_lengths() {
+        let date = Value::DateTime(DateTime::ymd(2020, 1, 31));
+        let result = (date + Value::Duration(Duration::months(1))).unwrap();
+        assert_eq!(result, Value::DateTime(DateTime::ymd(2020, 2, 29)));
+    }
+
+    #[test]
+    fn adding_days_and_time() {
+        let start = Value::DateTime(full(ymd(2020, 12, 31), 22, 0, TimeZone::Utc));
+        let duration = Value::Duration(Duration::from_str("P30DT3H").unwrap());
+        let result = (start + duration).unwrap();
+        assert_eq!(
+            result,
+            Value::DateTime(full(ymd(2021, 1, 31), 1, 0, TimeZone::Utc))
+        );
+    }
+
+    #[test]
+    fn days_between_dates() {
+        let a = Value::DateTime(DateTime::ymd(2021, 3, 1));
+        let b = Value::DateTime(DateTime::ymd(2020, 3, 1));
+        assert_eq!((a - b).unwrap(), Value::Duration(Duration::days(365)));
+    }
+
+    #[test]
+    fn difference_respects_timezone_offsets() {
+        let cet = TimeZone::Offset {
+            hours: 1,
+            minutes: 0,
+        };
+        let a = DateTime::Full {
+            date: ymd(2021, 1, 1),
+            time: Time {
+                hour: 12,
+                minute: 0,
+                second: 0,
+                milli: 0,
+                micro: 0,
+                nano: 0,
+                timezone: cet,
+            },
+        };
+        let b = full(ymd(2021, 1, 1), 10, 30, TimeZone::Utc);
+        let difference = a.duration_since(&b).unwrap();
+        assert_eq!(difference, Duration::seconds(30 * 60));
+    }
+
+    #[test]
+    fn adding_durations_at_the_extremes() {
+        let parser = Parser::new();
+        let date = parser.parse("\"2020-01-01\"").unwrap();
+        let days = Duration::from_str("P9223372036854700000D").unwrap();
+        let cases = vec![
+            (date.clone(), Duration::days(i64::MAX)),
+            (date.clone(), Duration::days(i64::MIN)),
+            (date.clone(), Duration::months(i64::MAX)),
+            (date.clone(), days.clone()),
+            (
+                Value::DateTime(full(ymd(2020, 1, 1), 12, 0, TimeZone::Utc)),
+                days.clone(),
+            ),
+        ];
+        for (start, duration) in cases {
+            match start.clone() + Value::Duration(duration.clone()) {
+                Err(ArithmeticError::UndefinedOperation(ArithmeticOperation::Add, _)) => {}
+                result => panic!("Unexpected result {:?} for {:?}", result, duration),
+            }
+        }
+        match date - Value::Duration(days) {
+            Err(ArithmeticError::UndefinedOperation(ArithmeticOperation::Sub, _)) => {}
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn time_durations_do_not_apply_to_dates() {
+        let date = Value::DateTime(DateTime::ymd(2021, 3, 1));
+        let result = date + Value::Duration(Duration::seconds(60));
+        result.unwrap_err();
+    }
+
+    #[test]
+    fn duration_type_constraint() {
+        let contract = ValueContract::new(
+            TypeConstraint::IsType(ValueType::Duration),
+            vec![ValueConstraint::Maximum(Value::Duration(Duration::days(7)))],
+        );
+        contract
+            .validate(&Value::Duration(Duration::days(3)))
+            .unwrap();
+        contract
+            .validate(&Value::Duration(Duration::days(8)))
+            .unwrap_err();
+        contract.validate(&Value::from(3)).unwrap_err();
+    }
+}
+
+#[cfg(feature = "timezones")]
+mod timezones {
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use std::str::FromStr;
+
+    use crate::fixtures::*;
+
+    fn zone(name: &str) -> TimeZone {
+        TimeZone::from_str(name).unwrap()
+    }
+
+    #[test]
+    fn timezone_parsing() {
+        assert_eq!(TimeZone::from_str("Z").unwrap(), TimeZone::Utc);
+        assert_eq!(TimeZone::from_str("+00:00").unwrap(), TimeZone::Utc);
+        assert_eq!(
+            TimeZone::from_str("-05:30").unwrap(),
+            TimeZone::Offset {
+                hours: -5,
+                minutes: -30
+            }
+        );
+        assert_eq!(
+            TimeZone::from_str("Europe/Luxembourg").unwrap(),
+            TimeZone::Named("Europe/Luxembourg".to_string())
+        );
+        TimeZone::from_str("Europe/Atlantis").unwrap_err();
+        TimeZone::from_str("+25:00").unwrap_err();
+    }
+
+    #[test]
+    fn conversion_follows_daylight_saving_time() {
+        let winter = full(ymd(2021, 1, 15), 10, 0, zone("Z"));
+        let summer = full(ymd(2021, 7, 15), 10, 0, zone("Z"));
+        let luxembourg = TimeZone::from_str("Europe/Luxembourg").unwrap();
+
+        assert_eq!(
+            winter.to_timezone(&luxembourg).unwrap(),
+            full(ymd(2021, 1, 15), 11, 0, zone("Europe/Luxembourg"))
+        );
+        assert_eq!(
+            summer.to_timezone(&luxembourg).unwrap(),
+            full(ymd(2021, 7, 15), 12, 0, zone("Europe/Luxembourg"))
+        );
+        assert_eq!(
+            full(ymd(2021, 7, 15), 12, 0, zone("Europe/Luxembourg"))
+                .to_utc()
+                .unwrap(),
+            summer
+        );
+    }
+
+    #[test]
+    fn conversion_crosses_date_boundaries() {
+        let datetime = full(ymd(2021, 12, 31), 23, 30, zone("+01:00"));
+        let tokyo = TimeZone::from_str("Asia/Tokyo").unwrap();
+        assert_eq!(
+            datetime.to_timezone(&tokyo).unwrap(),
+            full(ymd(2022, 1, 1), 7, 30, zone("Asia/Tokyo"))
+        );
+    }
+
+    #[test]
+    fn skipped_and_repeated_wall_clock_times() {
+        // Clocks are turned forward from 02:00 to 03:00.
+        let skipped = full(ymd(2021, 3, 28), 2, 30, zone("Europe/Luxembourg"));
+        assert_eq!(skipped.to_utc(), None);
+        // Clocks are turned back from 03:00 to 02:00, the earlier instant wins.
+        assert_eq!(
+            full(ymd(2021, 10, 31), 2, 30, zone("Europe/Luxembourg"))
+                .to_utc()
+                .unwrap(),
+            full(ymd(2021, 10, 31), 0, 30, zone("Z"))
+        );
+    }
+
+    #[test]
+    fn exact_durations_are_added_to_the_instant() {
+        let before = full(ymd(2021, 3, 28), 1, 30, zone("Europe/Luxembourg"));
+        assert_eq!(
+            before.checked_add(&Duration::seconds(3600)).unwrap(),
+            full(ymd(2021, 3, 28), 3, 30, zone("Europe/Luxembourg"))
+        );
+        assert_eq!(
+            before.checked_add(&Duration::days(1)).unwrap(),
+            full(ymd(2021, 3, 29), 1, 30, zone("Europe/Luxembourg"))
+        );
+    }
+
+    #[test]
+    fn bounds_normalized_to_utc() {
+        let max = Value::DateTime(full(ymd(2021, 1, 1), 11, 0, zone("Z")));
+        let value = Value::DateTime(full(ymd(2021, 1, 1), 12, 0, zone("+02:00")));
+
+        ValueConstraint::Maximum(max.clone())
+            .validate(&value)
+            .unwrap_err();
+        ValueConstraint::NormalizedToUtc(Box::new(ValueConstraint::Maximum(max.clone())))
+            .validate(&value)
+            .unwrap();
+
+        let later = Value::DateTime(full(ymd(2021, 1, 1), 12, 30, zone("Europe/Luxembourg")));
+        ValueConstraint::NormalizedToUtc(Box::new(ValueConstraint::Maximum(max)))
+            .validate(&later)
+            .unwrap_err();
+    }
+}
+
+mod time_parsing {
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    fn parse_time(literal: &str) -> Option<Time> {
+        match Parser::new().parse(&format!("\"{}\"", literal)).unwrap() {
+            Value::DateTime(DateTime::Time(time)) => Some(time),
+            Value::DateTime(DateTime::Full { time, .. }) => Some(time),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn nanosecond_precision() {
+        let time = parse_time("12:30:15.123456789").unwrap();
+        assert_eq!((time.milli, time.micro, time.nano), (123, 456, 789));
+
+        let time = parse_time("2021-05-04T12:30:15,0000017Z").unwrap();
+        assert_eq!((time.milli, time.micro, time.nano), (0, 1, 700));
+
+        // Digits beyond nanoseconds are truncated.
+        let time = parse_time("12:30:15.9999999999").unwrap();
+        assert_eq!((time.milli, time.micro, time.nano), (999, 999, 999));
+    }
+
+    #[test]
+    fn offsets() {
+        let time = parse_time("12:30:15.5-05:30").unwrap();
+        assert_eq!(
+            time.timezone,
+            TimeZone::Offset {
+                hours: -5,
+                minutes: -30
+            }
+        );
+        assert_eq!(time.milli, 500);
+    }
+
+    #[test]
+    fn leap_seconds() {
+        let time = parse_time("23:59:60Z").unwrap();
+        assert!(time.is_leap_second());
+        parse_time("2016-12-31T23:59:60.5Z").unwrap();
+        parse_time("2017-01-01T00:59:60+01:00").unwrap();
+
+        // Leap seconds only happen at the end of a UTC day.
+        assert_eq!(parse_time("12:59:60Z"), None);
+        assert_eq!(parse_time("23:59:60+01:00"), None);
+    }
+
+    #[test]
+    fn end_of_day() {
+        let value = Parser::new().parse("\"2021-12-31T24:00:00Z\"").unwrap();
+        assert_eq!(
+            value,
+            Value::DateTime(DateTime::Full {
+                date: Date::YearMonthDay {
+                    year: 2022,
+                    month: 1,
+                    day: 1
+                },
+                time: Time {
+                    hour: 0,
+                    minute: 0,
+                    second: 0,
+                    milli: 0,
+                    micro: 0,
+                    nano: 0,
+                    timezone: TimeZone::Utc,
+                },
+            })
+        );
+        assert_eq!(parse_time("24:00:01"), None);
+    }
+
+    #[test]
+    fn leap_seconds_in_arithmetic() {
+        let leap = match Parser::new().parse("\"2016-12-31T23:59:60Z\"").unwrap() {
+            Value::DateTime(datetime) => datetime,
+            _ => unreachable!(),
+        };
+        let later = leap.checked_add(&Duration::seconds(1)).unwrap();
+        assert_eq!(
+            later,
+            DateTime::Full {
+                date: Date::YearMonthDay {
+                    year: 2017,
+                    month: 1,
+                    day: 1
+                },
+                time: Time {
+                    hour: 0,
+                    minute: 0,
+                    second: 1,
+                    milli: 0,
+                    micro: 0,
+                    nano: 0,
+                    timezone: TimeZone::Utc,
+                },
+            }
+        );
+    }
+}
+
+mod datetime_formatting {
+    use datakit::errors::*;
+    use datakit::value::coercion::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    #[cfg(feature = "timezones")]
+    use crate::fixtures::parse;
+
+    #[cfg(feature = "timezones")]
+    fn to_text(value: &Value) -> String {
+        match Coercion::new().convert(value, &ValueType::Text).unwrap() {
+            Value::Text(text) => text,
+            other => panic!("Unexpected value {:?}", other),
+        }
+    }
+
+    fn sample() -> DateTime {
+        DateTime::Full {
+            date: Date::YearMonthDay {
+                year: 2021,
+                month: 7,
+                day: 4,
+            },
+            time: Time {
+                hour: 14,
+                minute: 5,
+                second: 9,
+                milli: 250,
+                micro: 0,
+                nano: 0,
+                timezone: TimeZone::Offset {
+                    hours: 2,
+                    minutes: 0,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn iso8601_display() {
+        assert_eq!(sample().to_string(), "2021-07-04T14:05:09.25+02:00");
+        assert_eq!(DateTime::hms(8, 0, 0).to_string(), "08:00:00Z");
+        assert_eq!(
+            DateTime::hms_mmn(8, 0, 0, 0, 0, 1).to_string(),
+            "08:00:00.000000001Z"
+        );
+        assert_eq!(DateTime::ymd(-44, 3, 15).to_string(), "-0044-03-15");
+    }
+
+    #[cfg(feature = "timezones")]
+    #[test]
+    fn text_roundtrips_through_parser() {
+        for literal in &[
+            "2021-07-04",
+            "2021-W26-7",
+            "2021-185",
+            "23:59:59.123456789-05:30",
+            "2021-07-04T14:05:09.25+02:00",
+            "2021-07-04T14:05:09Z",
+            "2021-07-04T14:05:09+02:00[Europe/Luxembourg]",
+            "14:05:09[Europe/Luxembourg]",
+        ] {
+            let value = parse(literal);
+            assert!(matches!(value, Value::DateTime(_)), "{}", literal);
+            assert_eq!(to_text(&value), *literal);
+            assert_eq!(parse(&to_text(&value)), value);
+        }
+    }
+
+    #[cfg(feature = "timezones")]
+    #[test]
+    fn zone_names_must_match_offsets() {
+        assert_eq!(
+            parse("2021-07-04T14:05:09+01:00[Europe/Luxembourg]"),
+            Value::Text("2021-07-04T14:05:09+01:00[Europe/Luxembourg]".into())
+        );
+        assert_eq!(
+            parse("2021-07-04T14:05:09[Europe/Luxembourg]"),
+            parse("2021-07-04T14:05:09+02:00[Europe/Luxembourg]")
+        );
+    }
+
+    #[test]
+    fn pattern_formatting() {
+        let format = |pattern: &str| {
+            sample()
+                .format(&DateTimeFormat::new(pattern).unwrap())
+                .unwrap()
+        };
+        assert_eq!(format("%F %T%.f %:z"), "2021-07-04 14:05:09.25 +02:00");
+        assert_eq!(format("%a, %d %b %y %I:%M %p"), "Sun, 04 Jul 21 02:05 PM");
+        assert_eq!(format("%G-W%V-%u, day %j"), "2021-W26-7, day 185");
+        assert_eq!(format("%H%M%S.%3f%z"), "140509.250+0200");
+        assert_eq!(format("100%%"), "100%");
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        DateTimeFormat::new("%Q").unwrap_err();
+        DateTimeFormat::new("%4f").unwrap_err();
+        DateTimeFormat::new("%").unwrap_err();
+
+        let time_only = DateTimeFormat::new("%H:%M").unwrap();
+        assert_eq!(
+            DateTime::ymd(2021, 7, 4).format(&time_only),
+            Err(FormattingError::MissingComponent("time".into()))
+        );
+    }
+
+    #[test]
+    fn coercion_with_datetime_format() {
+        let coercion =
+            Coercion::new().with_datetime_format(DateTimeFormat::new("%d.%m.%Y").unwrap());
+        assert_eq!(
+            coercion
+                .convert(&Value::DateTime(sample()), &ValueType::Text)
+                .unwrap(),
+            Value::Text("04.07.2021".into())
+        );
+        coercion
+            .convert(&Value::DateTime(DateTime::hms(8, 0, 0)), &ValueType::Text)
+            .unwrap_err();
+    }
+}
+
+mod date_formats {
+    use datakit::errors::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::DateParser;
+    use datakit::value::primitives::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Value {
+        Value::DateTime(DateTime::ymd(year, month, day))
+    }
+
+    #[test]
+    fn pattern_parsing() {
+        let parse = |pattern: &str, s: &str| DateTimeFormat::new(pattern).unwrap().parse(s);
+
+        assert_eq!(
+            parse("%d %b %Y", "4 Mar 2021").unwrap(),
+            DateTime::ymd(2021, 3, 4)
+        );
+        assert_eq!(
+            parse("%d %B %Y", "04 MARCH 2021").unwrap(),
+            DateTime::ymd(2021, 3, 4)
+        );
+        assert_eq!(
+            parse("%Y%m%d", "20210304").unwrap(),
+            DateTime::ymd(2021, 3, 4)
+        );
+        assert_eq!(
+            parse("%d/%m/%y", "03/04/21").unwrap(),
+            DateTime::ymd(2021, 4, 3)
+        );
+        assert_eq!(
+            parse("%a, %d %b %Y", "Thu, 04 Mar 2021").unwrap(),
+            DateTime::ymd(2021, 3, 4)
+        );
+        assert_eq!(
+            parse("%I:%M %p", "2:30 pm").unwrap(),
+            DateTime::hms_mmn_tz(14, 30, 0, 0, 0, 0, TimeZone::Unspecified)
+        );
+        assert_eq!(
+            parse("%d.%m.%Y %H:%M:%S%.f %:z", "04.03.2021 14:30:00.5 +01:00").unwrap(),
+            DateTime::Full {
+                date: Date::YearMonthDay {
+                    year: 2021,
+                    month: 3,
+                    day: 4
+                },
+                time: Time {
+                    hour: 14,
+                    minute: 30,
+                    second: 0,
+                    milli: 500,
+                    micro: 0,
+                    nano: 0,
+                    timezone: TimeZone::Offset {
+                        hours: 1,
+                        minutes: 0
+                    },
+                },
+            }
+        );
+
+        parse("%d/%m/%Y", "31/02/2021").unwrap_err();
+        parse("%d/%m/%Y", "04/03/2021 extra").unwrap_err();
+        parse("%a, %d %b %Y", "Fri, 04 Mar 2021").unwrap_err();
+        parse("%Y%m%d", "202103").unwrap_err();
+    }
+
+    #[test]
+    fn mixed_formats_in_a_column() {
+        let parser = DateParser::from_patterns(&["%d/%m/%Y", "%d %b %Y", "%Y%m%d"]).unwrap();
+        let column = parser
+            .parse_column(&["03/04/2021", "4 Mar 2021", "20210304", ""])
+            .unwrap();
+        assert_eq!(
+            column,
+            vec![
+                date(2021, 4, 3),
+                date(2021, 3, 4),
+                date(2021, 3, 4),
+                Value::Missing(Empty::Expected)
+            ]
+        );
+    }
+
+    #[test]
+    fn column_settles_day_month_order() {
+        let parser = DateParser::from_patterns(&["%m/%d/%Y", "%d/%m/%Y"]).unwrap();
+        let column = parser
+            .parse_column(&["03/04/2021", "25/12/2021", "4 Mar 2021"])
+            .unwrap_err();
+        assert!(
+            matches!(column, ParsingError::CannotParseValue { ref value, .. } if value == "4 Mar 2021")
+        );
+
+        let column = parser
+            .parse_column(&["03/04/2021", "25/12/2021", "05/05/2021"])
+            .unwrap();
+        assert_eq!(
+            column,
+            vec![date(2021, 4, 3), date(2021, 12, 25), date(2021, 5, 5)]
+        );
+    }
+
+    #[test]
+    fn ambiguous_column() {
+        let parser = DateParser::from_patterns(&["%d/%m/%Y", "%m/%d/%Y"]).unwrap();
+        match parser.parse_column(&["03/04/2021", "05/05/2021", "01/02/2021"]) {
+            Err(ParsingError::AmbiguousDate { value, patterns }) => {
+                assert_eq!(value, "03/04/2021");
+                assert_eq!(patterns, vec!["%d/%m/%Y", "%m/%d/%Y"]);
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+
+        parser.parse("03/04/2021").unwrap_err();
+        assert_eq!(parser.parse("05/05/2021").unwrap(), date(2021, 5, 5));
+    }
+}
+
+mod epoch_timestamps {
+    use datakit::value::coercion::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::Parser;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use std::str::FromStr;
+
+    use crate::fixtures::*;
+
+    #[test]
+    fn number_to_datetime() {
+        let coercion = Coercion::new();
+        assert_eq!(
+            coercion
+                .convert(&Value::from(1_614_816_000), &ValueType::DateTime)
+                .unwrap(),
+            parse("2021-03-04T00:00:00Z")
+        );
+        assert_eq!(
+            coercion
+                .convert(&Value::from(-1), &ValueType::DateTime)
+                .unwrap(),
+            parse("1969-12-31T23:59:59Z")
+        );
+        assert_eq!(
+            coercion
+                .convert(&Value::from(1.5), &ValueType::DateTime)
+                .unwrap(),
+            parse("1970-01-01T00:00:01.5Z")
+        );
+
+        let millis = Coercion::new().with_epoch_unit(EpochUnit::Milliseconds);
+        assert_eq!(
+            millis
+                .convert(&Value::from(1_614_816_000_123i64), &ValueType::DateTime)
+                .unwrap(),
+            parse("2021-03-04T00:00:00.123Z")
+        );
+    }
+
+    #[test]
+    fn datetime_to_number() {
+        let coercion = Coercion::new();
+        assert_eq!(
+            coercion
+                .convert(&parse("2021-03-04T01:00:00+01:00"), &ValueType::Number)
+                .unwrap(),
+            Value::from(1_614_816_000)
+        );
+        assert_eq!(
+            coercion
+                .convert(&parse("1970-01-01T00:00:01.25Z"), &ValueType::Number)
+                .unwrap(),
+            Value::from(BigDecimal::from_str("1.25").unwrap())
+        );
+
+        let nanos = Coercion::new().with_epoch_unit(EpochUnit::Nanoseconds);
+        assert_eq!(
+            nanos
+                .convert(&parse("2021-03-04T00:00:00.000000001Z"), &ValueType::Number)
+                .unwrap(),
+            Value::from(1_614_816_000_000_000_001i64)
+        );
+
+        coercion
+            .convert(&parse("2021-03-04"), &ValueType::Number)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn out_of_range_timestamps() {
+        Coercion::new()
+            .convert(&Value::from(i64::MAX), &ValueType::DateTime)
+            .unwrap_err();
+        Coercion::new()
+            .convert(&Value::from(f64::NAN), &ValueType::DateTime)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn parser_epoch_option() {
+        let parser = Parser::new().with_epoch_unit(Some(EpochUnit::Milliseconds));
+        let column: Vec<Value> = ["1614816000000", "0", "\"text\""]
+            .iter()
+            .map(|s| parser.parse(s).unwrap())
+            .collect();
+        assert_eq!(
+            column,
+            vec![
+                parse("2021-03-04T00:00:00Z"),
+                parse("1970-01-01T00:00:00Z"),
+                Value::Text("text".into())
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "chrono_dates")]
+mod chrono_conversions {
+    use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone as _, Utc};
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use std::convert::TryFrom;
+
+    use crate::fixtures::*;
+
+    #[test]
+    fn naive_types() {
+        let date = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap();
+        assert_eq!(Value::from(date), parse("2020-02-29"));
+        assert_eq!(Option::<NaiveDate>::from(parse("2020-W09-6")), Some(date));
+
+        let time = NaiveTime::from_hms_nano_opt(23, 59, 59, 1_123_456_789).unwrap();
+        assert_eq!(Value::from(time), parse("23:59:60.123456789"));
+        assert_eq!(
+            Option::<NaiveTime>::from(parse("23:59:60.123456789Z")),
+            Some(time)
+        );
+
+        let datetime = date.and_hms_milli_opt(12, 30, 0, 250).unwrap();
+        assert_eq!(Value::from(datetime), parse("2020-02-29T12:30:00.25"));
+    }
+
+    #[test]
+    fn zoned_types() {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let datetime = offset.with_ymd_and_hms(2021, 7, 4, 14, 5, 9).unwrap();
+        let value = parse("2021-07-04T14:05:09+02:00");
+        assert_eq!(Value::from(datetime), value);
+        assert_eq!(
+            Option::<chrono::DateTime<FixedOffset>>::from(value.clone()),
+            Some(datetime)
+        );
+        assert_eq!(
+            Option::<chrono::DateTime<Utc>>::from(value),
+            Some(Utc.with_ymd_and_hms(2021, 7, 4, 12, 5, 9).unwrap())
+        );
+        assert_eq!(
+            Option::<chrono::DateTime<Utc>>::from(parse("12:00:00")),
+            None
+        );
+    }
+
+    #[cfg(feature = "timezones")]
+    #[test]
+    fn named_zones() {
+        let zone: chrono_tz::Tz = "Europe/Luxembourg".parse().unwrap();
+        let datetime = zone.with_ymd_and_hms(2021, 7, 4, 14, 5, 9).unwrap();
+        let converted = DateTime::from_chrono_tz(datetime);
+        assert_eq!(
+            Value::DateTime(converted.clone()),
+            parse("2021-07-04T14:05:09[Europe/Luxembourg]")
+        );
+        assert_eq!(
+            chrono::DateTime::<chrono_tz::Tz>::try_from(&converted).unwrap(),
+            datetime
+        );
+    }
+
+    #[test]
+    fn calendar_agrees_with_chrono() {
+        for days in (-800_000..800_000).step_by(997) {
+            let date = Date::from_epoch_days(days).unwrap();
+            let chrono_date = NaiveDate::try_from(&date).unwrap();
+            assert_eq!(Date::from(chrono_date), date);
+            assert_eq!(
+                NaiveDate::try_from(&date.to_year_week_day().unwrap()).unwrap(),
+                chrono_date
+            );
+        }
+    }
+}
+
+mod temporal_constraints {
+    use datakit::errors::*;
+    use datakit::value::clock::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    use crate::fixtures::*;
+
+    fn datetime(literal: &str) -> DateTime {
+        match parse(literal) {
+            Value::DateTime(datetime) => datetime,
+            other => panic!("Not a date time: {:?}", other),
+        }
+    }
+
+    fn clock() -> FixedClock {
+        FixedClock(datetime("2021-03-04T12:00:00Z"))
+    }
+
+    fn is_valid(constraint: &ValueConstraint, literal: &str) -> bool {
+        constraint
+            .validate_with_clock(&parse(literal), &clock())
+            .is_ok()
+    }
+
+    #[test]
+    fn not_in_the_future() {
+        let constraint = ValueConstraint::NotAfter(TemporalBound::Now);
+        assert!(is_valid(&constraint, "2021-03-04T12:00:00Z"));
+        assert!(is_valid(&constraint, "2021-03-04T13:00:00+02:00"));
+        assert!(!is_valid(&constraint, "2021-03-04T12:00:01Z"));
+        assert!(is_valid(&constraint, "2021-03-04"));
+        assert!(!is_valid(&constraint, "2021-03-05"));
+    }
+
+    #[test]
+    fn within_days_of_today() {
+        let constraint = ValueConstraint::NotBefore(TemporalBound::FromNow(Duration::days(-90)));
+        assert!(is_valid(&constraint, "2020-12-04"));
+        assert!(!is_valid(&constraint, "2020-12-03"));
+        assert!(is_valid(&constraint, "2020-12-04T12:00:00Z"));
+        assert!(!is_valid(&constraint, "2020-12-04T11:59:59Z"));
+    }
+
+    #[test]
+    fn fixed_bound() {
+        let constraint =
+            ValueConstraint::NotBefore(TemporalBound::At(datetime("2000-01-01T00:00:00Z")));
+        assert!(is_valid(&constraint, "2000-01-01T01:00:00+01:00"));
+        assert!(!is_valid(&constraint, "1999-12-31T23:59:59Z"));
+        assert!(constraint.validate(&parse("2021-03-04")).is_ok());
+    }
+
+    #[test]
+    fn times_and_mismatched_values_are_invalid_constraints() {
+        let constraint = ValueConstraint::NotAfter(TemporalBound::At(datetime("12:00:00Z")));
+        assert!(is_valid(&constraint, "13:00:00+02:00"));
+        assert!(!is_valid(&constraint, "12:00:01Z"));
+
+        match constraint.validate_with_clock(&parse("2021-03-04"), &clock()) {
+            Err(ValidationError::ValueValidationError {
+                failed_constraints, ..
+            }) => assert!(matches!(
+                failed_constraints.as_slice(),
+                [ConstraintError::InvalidConstraintError]
+            )),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(ValueConstraint::DateOnly.validate(&Value::from(1)).is_err());
+    }
+
+    #[test]
+    fn weekdays() {
+        let weekend = ValueConstraint::Weekdays(vec![6, 7]);
+        assert!(is_valid(&weekend, "2021-03-06"));
+        assert!(is_valid(&weekend, "2021-03-07T23:30:00-05:00"));
+        assert!(!is_valid(&weekend, "2021-03-04"));
+        assert!(is_valid(&weekend, "2021-W09-6"));
+    }
+
+    #[test]
+    fn business_days() {
+        let constraint = ValueConstraint::BusinessDay {
+            holidays: vec![Date::YearMonthDay {
+                year: 2021,
+                month: 12,
+                day: 24,
+            }],
+        };
+        assert!(is_valid(&constraint, "2021-03-04"));
+        assert!(!is_valid(&constraint, "2021-03-06"));
+        assert!(!is_valid(&constraint, "2021-12-24"));
+        assert!(!is_valid(&constraint, "2021-12-24T10:00:00Z"));
+    }
+
+    #[test]
+    fn date_only_and_time_zones() {
+        assert!(is_valid(&ValueConstraint::DateOnly, "2021-03-04"));
+        assert!(!is_valid(
+            &ValueConstraint::DateOnly,
+            "2021-03-04T12:00:00Z"
+        ));
+
+        assert!(is_valid(&ValueConstraint::HasTimeZone, "12:00:00+01:00"));
+        assert!(is_valid(
+            &ValueConstraint::HasTimeZone,
+            "2021-03-04T12:00:00Z"
+        ));
+        assert!(!is_valid(
+            &ValueConstraint::HasTimeZone,
+            "2021-03-04T12:00:00"
+        ));
+        assert!(!is_valid(&ValueConstraint::HasTimeZone, "2021-03-04"));
+    }
+
+    #[test]
+    fn contracts_use_the_clock() {
+        let contract = ValueContract::new(
+            TypeConstraint::IsType(ValueType::DateTime),
+            vec![
+                ValueConstraint::NotAfter(TemporalBound::Now),
+                ValueConstraint::HasTimeZone,
+            ],
+        );
+        let future = parse("2021-03-05T00:00:00Z");
+        assert!(contract.validate_with_clock(&future, &clock()).is_err());
+        let later = FixedClock(datetime("2021-03-06T00:00:00Z"));
+        assert!(contract.validate_with_clock(&future, &later).is_ok());
+    }
+}
+
+mod parser_options {
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use std::str::FromStr;
+
+    fn parse(options: &ParserOptions, literal: &str) -> Option<Value> {
+        Parser::new()
+            .with_options(options.clone())
+            .parse(literal)
+            .ok()
+    }
+
+    #[test]
+    fn defaults_follow_json() {
+        let options = ParserOptions::new();
+        assert_eq!(parse(&options, "1.5"), Some(Value::from(1.5)));
+        assert_eq!(parse(&options, "-12"), Some(Value::from(-12)));
+        assert_eq!(parse(&options, "1e3"), Some(Value::from(1000.0)));
+        assert_eq!(parse(&options, "1,5"), None);
+        assert_eq!(parse(&options, "1 234"), None);
+        assert_eq!(parse(&options, "12%"), None);
+        assert_eq!(parse(&options, "$4.99"), None);
+        assert_eq!(parse(&options, "01"), None);
+    }
+
+    #[test]
+    fn separators() {
+        let german = ParserOptions::new()
+            .with_decimal_separator(',')
+            .with_thousands_separator(Some('.'));
+        assert_eq!(parse(&german, "1.234,56"), Some(Value::from(1234.56)));
+        assert_eq!(parse(&german, "-1.234.567"), Some(Value::from(-1234567)));
+        assert_eq!(parse(&german, "0,5"), Some(Value::from(0.5)));
+        assert_eq!(parse(&german, "1.23,4"), None);
+        assert_eq!(parse(&german, "1,2,3"), None);
+
+        let french = ParserOptions::new()
+            .with_decimal_separator(',')
+            .with_thousands_separator(Some(' '));
+        assert_eq!(parse(&french, "1 234"), Some(Value::from(1234)));
+        assert_eq!(parse(&french, "1\u{a0}234,5"), Some(Value::from(1234.5)));
+        assert_eq!(parse(&french, "1.5"), None);
+
+        let english = ParserOptions::new().with_thousands_separator(Some(','));
+        assert_eq!(parse(&english, "1,234.5"), Some(Value::from(1234.5)));
+        assert_eq!(parse(&english, "12,34"), None);
+    }
+
+    #[test]
+    fn percent() {
+        let strip = ParserOptions::new().with_percent(PercentHandling::Strip);
+        assert_eq!(parse(&strip, "12%"), Some(Value::from(12)));
+        let fraction = ParserOptions::new().with_percent(PercentHandling::Fraction);
+        assert_eq!(parse(&fraction, "12 %"), Some(Value::from(0.12)));
+        assert_eq!(parse(&fraction, "250%"), Some(Value::from(2.5)));
+        assert_eq!(parse(&fraction, "300%"), Some(Value::from(3)));
+        assert_eq!(parse(&fraction, "12"), Some(Value::from(12)));
+
+        let exact = Parser::new()
+            .with_exact_decimals(true)
+            .with_options(fraction);
+        assert_eq!(
+            exact.parse("12.5%").unwrap(),
+            Value::from(BigDecimal::from_str("0.125").unwrap())
+        );
+    }
+
+    #[test]
+    fn currency() {
+        let options = ParserOptions::new().with_currency_symbols(&["$", "EUR"]);
+        assert_eq!(parse(&options, "$4.99"), Some(Value::from(4.99)));
+        assert_eq!(parse(&options, "-$4.99"), Some(Value::from(-4.99)));
+        assert_eq!(parse(&options, "$-4.99"), Some(Value::from(-4.99)));
+        assert_eq!(parse(&options, "4.99 EUR"), Some(Value::from(4.99)));
+        assert_eq!(parse(&options, "4.99 GBP"), None);
+    }
+
+    #[test]
+    fn tokens() {
+        let options = ParserOptions::new()
+            .with_true_tokens(&["yes", "Y"])
+            .with_false_tokens(&["no", "N"])
+            .with_null_tokens(&["NA", "-"]);
+        assert_eq!(parse(&options, "yes"), Some(Value::from(true)));
+        assert_eq!(parse(&options, " N "), Some(Value::from(false)));
+        assert_eq!(parse(&options, "true"), Some(Value::from(true)));
+        assert_eq!(parse(&options, "NA"), Some(Value::Missing(Empty::Expected)));
+        assert_eq!(parse(&options, "-"), Some(Value::Missing(Empty::Expected)));
+        assert_eq!(parse(&options, "YES"), None);
+    }
+}
+
+mod bare_literals {
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    fn parse(literal: &str) -> Value {
+        Parser::new()
+            .with_options(ParserOptions::bare())
+            .parse(literal)
+            .unwrap()
+    }
+
+    #[test]
+    fn detected_values() {
+        assert_eq!(parse("42"), Value::from(42));
+        assert_eq!(parse(" -1.5 "), Value::from(-1.5));
+        assert_eq!(parse("1+2i"), Value::Number(Numeric::Complex(1.0, 2.0)));
+        assert_eq!(parse("true"), Value::from(true));
+        assert_eq!(parse("2021-03-04"), Value::from(DateTime::ymd(2021, 3, 4)));
+    }
+
+    #[test]
+    fn everything_else_is_text() {
+        assert_eq!(parse("hello"), Value::Text("hello".into()));
+        assert_eq!(parse("hello world "), Value::Text("hello world ".into()));
+        assert_eq!(parse("2021-02-30"), Value::Text("2021-02-30".into()));
+        assert_eq!(parse("P1D"), Value::Text("P1D".into()));
+        assert_eq!(parse("1,5"), Value::Text("1,5".into()));
+        assert_eq!(parse("[1, 2]"), Value::Text("[1, 2]".into()));
+        assert_eq!(parse("\"quoted\""), Value::Text("\"quoted\"".into()));
+        assert_eq!(parse("null"), Value::Text("null".into()));
+    }
+
+    #[test]
+    fn sentinels() {
+        for sentinel in ["", " ", "NA", "NULL", "-"].iter() {
+            assert_eq!(parse(sentinel), Value::Missing(Empty::Expected));
+        }
+
+        let parser = Parser::new().with_options(ParserOptions::bare().with_null_tokens(&["n/a"]));
+        assert_eq!(
+            parser.parse("n/a").unwrap(),
+            Value::Missing(Empty::Expected)
+        );
+        assert_eq!(parser.parse("NA").unwrap(), Value::Text("NA".into()));
+    }
+
+    #[test]
+    fn locale_numbers() {
+        let options = ParserOptions::bare()
+            .with_decimal_separator(',')
+            .with_thousands_separator(Some('.'));
+        let parser = Parser::new().with_options(options);
+        assert_eq!(parser.parse("1.234,5").unwrap(), Value::from(1234.5));
+        assert_eq!(parser.parse("1.5").unwrap(), Value::Text("1.5".into()));
+    }
+}
+
+mod datetime_detection {
+    use datakit::errors::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    fn parser(detection: DateTimeDetection) -> Parser {
+        Parser::new().with_options(ParserOptions::new().with_datetime_detection(detection))
+    }
+
+    #[test]
+    fn policies() {
+        let always = parser(DateTimeDetection::Always);
+        assert!(matches!(
+            always.parse("\"2020-123\"").unwrap(),
+            Value::DateTime(_)
+        ));
+
+        let never = parser(DateTimeDetection::Never);
+        assert_eq!(
+            never.parse("\"2020-123\"").unwrap(),
+            Value::Text("2020-123".into())
+        );
+        assert_eq!(never.parse("\"P1D\"").unwrap(), Value::Text("P1D".into()));
+
+        let formats = parser(DateTimeDetection::Formats(vec![DateTimeFormat::new(
+            "%d/%m/%Y",
+        )
+        .unwrap()]));
+        assert_eq!(
+            formats.parse("\"04/03/2021\"").unwrap(),
+            Value::from(DateTime::ymd(2021, 3, 4))
+        );
+        assert_eq!(
+            formats.parse("\"2021W05\"").unwrap(),
+            Value::Text("2021W05".into())
+        );
+    }
+
+    #[test]
+    fn partial_matches_stay_text() {
+        let parser = Parser::new();
+        for literal in [
+            "2021W05",
+            "2021-W05",
+            "1234",
+            "1230Z",
+            "2020-01-01xyz",
+            "12:30 pm",
+        ]
+        .iter()
+        {
+            assert_eq!(
+                parser.parse(&format!("\"{}\"", literal)).unwrap(),
+                Value::Text(literal.to_string())
+            );
+        }
+        assert_eq!(
+            parser.parse("\"T1230Z\"").unwrap(),
+            Value::DateTime(DateTime::hms(12, 30, 0))
+        );
+
+        let bare = Parser::new().with_options(ParserOptions::bare());
+        assert_eq!(
+            bare.parse("2100-XYZ").unwrap(),
+            Value::Text("2100-XYZ".into())
+        );
+        assert_eq!(
+            bare.parse("2020-01-01T10:00 UTC").unwrap(),
+            Value::Text("2020-01-01T10:00 UTC".into())
+        );
+    }
+
+    #[test]
+    fn ambiguous_formats() {
+        let formats = parser(DateTimeDetection::Formats(vec![
+            DateTimeFormat::new("%d/%m/%Y").unwrap(),
+            DateTimeFormat::new("%m/%d/%Y").unwrap(),
+        ]));
+        assert!(matches!(
+            formats.parse("\"03/04/2020\""),
+            Err(ParsingError::AmbiguousDate { .. })
+        ));
+        assert!(matches!(
+            formats.parse("[\"03/04/2020\"]"),
+            Err(ParsingError::AmbiguousDate { .. })
+        ));
+        assert!(matches!(
+            formats.parse_as("\"03/04/2020\"", &ValueType::DateTime),
+            Err(ParsingError::AmbiguousDate { .. })
+        ));
+        assert_eq!(
+            formats.parse("\"25/03/2020\"").unwrap(),
+            Value::from(DateTime::ymd(2020, 3, 25))
+        );
+        assert_eq!(
+            formats.parse("\"04/04/2020\"").unwrap(),
+            Value::from(DateTime::ymd(2020, 4, 4))
+        );
+    }
+
+    #[test]
+    fn bare_literals() {
+        let parser = Parser::new()
+            .with_options(ParserOptions::bare().with_datetime_detection(DateTimeDetection::Never));
+        assert_eq!(
+            parser.parse("2021-03-04").unwrap(),
+            Value::Text("2021-03-04".into())
+        );
+        assert_eq!(parser.parse("12").unwrap(), Value::from(12));
+    }
+
+    #[test]
+    fn parse_as_text() {
+        let parser = Parser::new();
+        assert_eq!(
+            parser.parse_as("\"2021W05\"", &ValueType::Text).unwrap(),
+            Value::Text("2021W05".into())
+        );
+        assert_eq!(
+            parser.parse_as("42", &ValueType::Text).unwrap(),
+            Value::Text("42".into())
+        );
+        assert_eq!(
+            parser.parse_as("null", &ValueType::Text).unwrap(),
+            Value::Missing(Empty::Expected)
+        );
+    }
+
+    #[test]
+    fn parse_as_datetime() {
+        let never = parser(DateTimeDetection::Never);
+        assert_eq!(
+            never
+                .parse_as("\"2021-03-04\"", &ValueType::DateTime)
+                .unwrap(),
+            Value::from(DateTime::ymd(2021, 3, 4))
+        );
+        assert!(never.parse_as("\"P1D\"", &ValueType::DateTime).is_err());
+        assert!(matches!(
+            never.parse_as("\"P1D\"", &ValueType::Duration).unwrap(),
+            Value::Duration(_)
+        ));
+
+        let formats = parser(DateTimeDetection::Formats(vec![DateTimeFormat::new(
+            "%d/%m/%Y",
+        )
+        .unwrap()]));
+        assert_eq!(
+            formats
+                .parse_as("\"04/03/2021\"", &ValueType::DateTime)
+                .unwrap(),
+            Value::from(DateTime::ymd(2021, 3, 4))
+        );
+    }
+
+    #[test]
+    fn parse_as_other_types() {
+        let parser = Parser::new();
+        assert_eq!(
+            parser.parse_as("\"42\"", &ValueType::Number).unwrap(),
+            Value::from(42)
+        );
+        assert!(parser.parse_as("\"abc\"", &ValueType::Number).is_err());
+        assert!(parser.parse_as("abc", &ValueType::Text).is_err());
+
+        let bare = Parser::new().with_options(ParserOptions::bare());
+        assert_eq!(
+            bare.parse_as("2021-03-04", &ValueType::Text).unwrap(),
+            Value::Text("2021-03-04".into())
+        );
+    }
+}
+
+mod parsing_errors {
+    use datakit::errors::*;
+    use datakit::value::definitions::*;
+    use datakit::value::parsing::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    fn details(
+        error: ParsingError,
+    ) -> (
+        Option<usize>,
+        Option<String>,
+        Option<ParsingCause>,
+        Option<ValueType>,
+    ) {
+        match error {
+            ParsingError::CannotParseValue {
+                offset,
+                expected,
+                cause,
+                target_type,
+                ..
+            } => (offset, expected, cause, target_type),
+            other => panic!("Unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn json_errors() {
+        let (offset, _, cause, _) = details(Parser::new().parse("[1, 2, x]").unwrap_err());
+        assert_eq!(offset, Some(7));
+        assert!(matches!(cause, Some(ParsingCause::Json(_))));
+
+        let (offset, _, _, _) = details(Parser::new().parse("{\"a\": 1,\n\"b\": }").unwrap_err());
+        assert_eq!(offset, Some(14));
+    }
+
+    #[test]
+    fn out_of_range_components() {
+        let cases = [
+            ("\"2021-02-29\"", "day"),
+            ("\"2021-366\"", "day of the year"),
+            ("\"24:30:00\"", "hour"),
+            ("\"12:00:60Z\"", "leap second"),
+        ];
+        for (literal, component) in cases.iter() {
+            let parser = Parser::new();
+            let (_, _, cause, target_type) =
+                details(parser.parse_as(literal, &ValueType::DateTime).unwrap_err());
+            assert_eq!(
+                cause,
+                Some(ParsingCause::OutOfRange(component.to_string())),
+                "{}",
+                literal
+            );
+            assert_eq!(target_type, Some(ValueType::DateTime));
+        }
+    }
+
+    #[test]
+    fn iso8601_errors() {
+        let (_, expected, cause, _) = details(
+            Parser::new()
+                .parse_as("\"next tuesday\"", &ValueType::DateTime)
+                .unwrap_err(),
+        );
+        assert_eq!(expected.as_deref(), Some("a date, time or date time"));
+        assert!(matches!(cause, Some(ParsingCause::Iso8601(_))));
+    }
+
+    #[test]
+    fn locale_number_errors() {
+        let parser = Parser::new().with_options(
+            ParserOptions::new()
+                .with_decimal_separator(',')
+                .with_thousands_separator(Some('.')),
+        );
+        let (offset, expected, _, _) = details(parser.parse("12.34,5").unwrap_err());
+        assert_eq!(offset, Some(3));
+        assert_eq!(expected.as_deref(), Some("groups of three digits"));
+
+        let (offset, expected, _, _) = details(parser.parse("-1.234,5.6").unwrap_err());
+        assert_eq!(offset, Some(8));
+        assert_eq!(expected.as_deref(), Some("a digit"));
+    }
+
+    #[test]
+    fn format_errors() {
+        let format = DateTimeFormat::new("%d %B %Y").unwrap();
+        let (offset, expected, _, _) = details(format.parse("04 Foo 2021").unwrap_err());
+        assert_eq!(offset, Some(3));
+        assert_eq!(expected.as_deref(), Some("a month name"));
+
+        let (offset, expected, _, _) = details(format.parse("04 March 2021 12:00").unwrap_err());
+        assert_eq!(offset, Some(13));
+        assert_eq!(expected.as_deref(), Some("the end of the text"));
+
+        let (_, _, cause, _) = details(format.parse("31 April 2021").unwrap_err());
+        assert_eq!(cause, Some(ParsingCause::OutOfRange("date".into())));
+    }
+
+    #[test]
+    fn display() {
+        let error = ParsingError::cannot_parse("12,5")
+            .at_offset(2)
+            .with_expected("a digit")
+            .with_target_type(&ValueType::Number);
+        assert_eq!(
+            error.to_string(),
+            "Cannot parse \"12,5\" as Number at byte 2, expected a digit"
+        );
+    }
+}
+
+mod text_coercion {
+    use datakit::errors::*;
+    use datakit::value::coercion::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    fn convert(text: &str, to: ValueType) -> Result<Value, CoercionError> {
+        Coercion::new().convert(&Value::Text(text.into()), &to)
+    }
+
+    fn fails(text: &str, to: ValueType) -> bool {
+        matches!(
+            convert(text, to.clone()),
+            Err(CoercionError::CoercionFailed { ref target_type, .. }) if *target_type == to
+        )
+    }
+
+    #[test]
+    fn results_have_the_target_type() {
+        assert_eq!(convert(" 42 ", ValueType::Number).unwrap(), Value::from(42));
+        assert_eq!(
+            convert("TRUE", ValueType::Boolean).unwrap(),
+            Value::from(true)
+        );
+        assert_eq!(
+            convert("2020-01-01", ValueType::DateTime).unwrap(),
+            Value::from(DateTime::ymd(2020, 1, 1))
+        );
+        assert_eq!(
+            convert("2020-01-01", ValueType::Text).unwrap(),
+            Value::Text("2020-01-01".into())
+        );
+    }
+
+    #[test]
+    fn other_literals_fail() {
+        assert!(fails("42", ValueType::Boolean));
+        assert!(fails("yes", ValueType::Boolean));
+        assert!(fails("2020-01-01", ValueType::Number));
+        assert!(fails("true", ValueType::Number));
+        assert!(fails("null", ValueType::Number));
+        assert!(fails("42", ValueType::DateTime));
+        assert!(fails("P1D", ValueType::DateTime));
+        assert!(fails("2020-01-01", ValueType::Duration));
+        assert!(matches!(
+            convert("abc", ValueType::Missing),
+            Err(CoercionError::CoercionImpossible { .. })
+        ));
+    }
+
+    #[test]
+    fn datetime_format() {
+        let coercion =
+            Coercion::new().with_datetime_format(DateTimeFormat::new("%d/%m/%Y").unwrap());
+        let convert =
+            |text: &str| coercion.convert(&Value::Text(text.into()), &ValueType::DateTime);
+        assert_eq!(
+            convert("04/03/2021").unwrap(),
+            Value::from(DateTime::ymd(2021, 3, 4))
+        );
+        assert_eq!(
+            convert("2021-03-04").unwrap(),
+            Value::from(DateTime::ymd(2021, 3, 4))
+        );
+    }
+}
+
+mod coercion_policies {
+    use datakit::errors::*;
+    use datakit::value::coercion::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    use crate::fixtures::*;
+
+    fn lossy(rounding: RoundingMode) -> Coercion {
+        Coercion::new()
+            .with_policy(CoercionPolicy::Lossy)
+            .with_rounding(rounding)
+    }
+
+    fn lost(result: Result<Value, CoercionError>, expected: LossKind) -> bool {
+        matches!(result, Err(CoercionError::InformationLoss(ref loss)) if loss.kind == expected)
+    }
+
+    #[test]
+    fn exact_conversions_are_allowed_by_all_policies() {
+        for policy in [
+            CoercionPolicy::Strict,
+            CoercionPolicy::Safe,
+            CoercionPolicy::Lossy,
+        ] {
+            let coercion = Coercion::new().with_policy(policy);
+            let mut losses = Vec::new();
+            assert_eq!(
+                coercion
+                    .convert_numeric_reporting(
+                        &Value::from(2.0),
+                        &NumericType::Integer,
+                        &mut losses
+                    )
+                    .unwrap(),
+                Value::from(2)
+            );
+            assert_eq!(
+                coercion
+                    .convert_numeric_reporting(&Value::from(3), &NumericType::Real, &mut losses)
+                    .unwrap(),
+                Value::from(3.0)
+            );
+            assert_eq!(
+                coercion
+                    .convert_numeric_reporting(&decimal("0.1"), &NumericType::Real, &mut losses)
+                    .unwrap(),
+                Value::from(0.1)
+            );
+            assert!(losses.is_empty());
+        }
+    }
+
+    #[test]
+    fn reals_become_integers_only_when_exact() {
+        let coercion = Coercion::new();
+        assert!(lost(
+            coercion.convert_numeric(&Value::from(2.5), &NumericType::Integer),
+            LossKind::Rounded
+        ));
+        assert!(lost(
+            coercion.convert_numeric(&decimal("1.01"), &NumericType::Unsigned),
+            LossKind::Rounded
+        ));
+        assert!(matches!(
+            coercion.convert_numeric(&Value::from(-1), &NumericType::Unsigned),
+            Err(CoercionError::CoercionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let round = |mode: RoundingMode, value: Value| {
+            lossy(mode)
+                .convert_numeric(&value, &NumericType::Integer)
+                .unwrap()
+        };
+        assert_eq!(
+            round(RoundingMode::HalfEven, Value::from(2.5)),
+            Value::from(2)
+        );
+        assert_eq!(
+            round(RoundingMode::HalfAwayFromZero, Value::from(2.5)),
+            Value::from(3)
+        );
+        assert_eq!(
+            round(RoundingMode::Truncate, Value::from(-2.7)),
+            Value::from(-2)
+        );
+        assert_eq!(
+            round(RoundingMode::Floor, Value::from(-2.2)),
+            Value::from(-3)
+        );
+        assert_eq!(
+            round(RoundingMode::Ceiling, decimal("2.01")),
+            Value::from(3)
+        );
+        assert_eq!(
+            round(RoundingMode::HalfEven, decimal("-3.5")),
+            Value::from(-4)
+        );
+
+        lossy(RoundingMode::Floor)
+            .convert_numeric(&Value::from(f64::NAN), &NumericType::Integer)
+            .unwrap_err();
+        lossy(RoundingMode::Floor)
+            .convert_numeric(&Value::from(1e30), &NumericType::Integer)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn imprecise_reals_are_reported() {
+        let large = Value::from(9_007_199_254_740_993i64);
+        assert!(lost(
+            Coercion::new().convert_numeric(&large, &NumericType::Real),
+            LossKind::Precision
+        ));
+        let long = decimal("0.12345678901234567890");
+        assert!(lost(
+            Coercion::new().convert_numeric(&long, &NumericType::Real),
+            LossKind::Precision
+        ));
+
+        let mut losses = Vec::new();
+        let real = lossy(RoundingMode::HalfEven)
+            .convert_numeric_reporting(&large, &NumericType::Real, &mut losses)
+            .unwrap();
+        assert_eq!(real, Value::from(9_007_199_254_740_992.0));
+        assert_eq!(
+            losses,
+            vec![CoercionLoss {
+                kind: LossKind::Precision,
+                original: large,
+                result: real,
+            }]
+        );
+    }
+
+    #[test]
+    fn text_trimming() {
+        let text = Value::Text(" true ".into());
+        let mut losses = Vec::new();
+        let result = Coercion::new()
+            .convert_reporting(&text, &ValueType::Boolean, &mut losses)
+            .unwrap();
+        assert_eq!(result, Value::from(true));
+        assert_eq!(losses.len(), 1);
+        assert_eq!(losses[0].kind, LossKind::TrimmedWhitespace);
+
+        let strict = Coercion::new().with_policy(CoercionPolicy::Strict);
+        assert!(lost(
+            strict.convert(&text, &ValueType::Boolean),
+            LossKind::TrimmedWhitespace
+        ));
+        assert_eq!(
+            strict
+                .convert(&Value::Text("true".into()), &ValueType::Boolean)
+                .unwrap(),
+            Value::from(true)
+        );
+    }
+
+    #[test]
+    fn text_losses_accumulate() {
+        let mut losses = Vec::new();
+        let result = lossy(RoundingMode::Truncate)
+            .convert_numeric_reporting(
+                &Value::Text(" 2.9 ".into()),
+                &NumericType::Integer,
+                &mut losses,
+            )
+            .unwrap();
+        assert_eq!(result, Value::from(2));
+        let kinds: Vec<_> = losses.iter().map(|loss| loss.kind).collect();
+        assert_eq!(kinds, vec![LossKind::Rounded, LossKind::TrimmedWhitespace]);
+    }
+
+    #[test]
+    fn datetime_truncation() {
+        let full = Value::from(DateTime::Full {
+            date: Date::YearMonthDay {
+                year: 2021,
+                month: 3,
+                day: 4,
+            },
+            time: Time {
+                hour: 23,
+                minute: 30,
+                second: 0,
+                milli: 0,
+                micro: 0,
+                nano: 0,
+                timezone: TimeZone::Utc,
+            },
+        });
+        assert!(lost(
+            Coercion::new().convert_datetime(&full, &DateTimeType::Date),
+            LossKind::TruncatedTime
+        ));
+
+        let mut losses = Vec::new();
+        let coercion = lossy(RoundingMode::HalfEven);
+        let date = coercion
+            .convert_datetime_reporting(&full, &DateTimeType::Date, &mut losses)
+            .unwrap();
+        assert_eq!(date, Value::from(DateTime::ymd(2021, 3, 4)));
+        let time = coercion
+            .convert_datetime_reporting(&full, &DateTimeType::Time, &mut losses)
+            .unwrap();
+        assert_eq!(time, Value::from(DateTime::hms(23, 30, 0)));
+        assert_eq!(losses[1].kind, LossKind::TruncatedDate);
+
+        coercion
+            .convert_datetime(&date, &DateTimeType::Full)
+            .unwrap_err();
+        assert_eq!(
+            coercion
+                .convert_datetime(&Value::Text("2021-03-04".into()), &DateTimeType::Date)
+                .unwrap(),
+            date
+        );
+    }
+}
+
+mod coercion_registry {
+    use datakit::errors::*;
+    use datakit::value::coercion::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+
+    fn yes_no(value: &Value, _: &ValueType) -> Result<Value, CoercionError> {
+        match value {
+            Value::Text(s) if s == "Y" => Ok(Value::from(true)),
+            Value::Text(s) if s == "N" => Ok(Value::from(false)),
+            _ => Err(CoercionError::CoercionFailed {
+                target_type: ValueType::Boolean,
+                source_value: value.clone(),
+            }),
+        }
+    }
+
+    /// Reads fiscal quarters like `FY2021Q2` as the first day of the
+    /// quarter, with fiscal years starting in April of the previous year.
+    fn fiscal_quarter(value: &Value, to: &ValueType) -> Result<Value, CoercionError> {
+        let failed = || CoercionError::CoercionFailed {
+            target_type: to.clone(),
+            source_value: value.clone(),
+        };
+        let text = match value {
+            Value::Text(text) => text,
+            _ => return Err(CoercionError::UnexpectedType),
+        };
+        let (year, quarter) = text
+            .strip_prefix("FY")
+            .and_then(|rest| rest.split_once('Q'))
+            .ok_or_else(failed)?;
+        let year: i32 = year.parse().map_err(|_| failed())?;
+        let quarter: u8 = quarter.parse().map_err(|_| failed())?;
+        if !(1..=4).contains(&quarter) {
+            return Err(failed());
+        }
+        let month = 4 + (quarter - 1) * 3;
+        Ok(match month {
+            13 => Value::from(DateTime::ymd(year, 1, 1)),
+            month => Value::from(DateTime::ymd(year - 1, month, 1)),
+        })
+    }
+
+    fn registry() -> CoercionRegistry {
+        CoercionRegistry::new()
+            .with_rule(ValueType::Text, ValueType::Boolean, yes_no)
+            .with_named_rule("fiscalQuarter", fiscal_quarter)
+    }
+
+    #[test]
+    fn rules_by_types() {
+        let registry = registry();
+        assert_eq!(
+            registry
+                .convert(&Value::Text("Y".into()), &ValueType::Boolean)
+                .unwrap(),
+            Value::from(true)
+        );
+        registry
+            .convert(&Value::Text("true".into()), &ValueType::Boolean)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn fallback_for_other_types() {
+        let registry = registry();
+        assert_eq!(
+            registry
+                .convert(&Value::Text("42".into()), &ValueType::Number)
+                .unwrap(),
+            Value::from(42)
+        );
+        assert_eq!(
+            registry
+                .convert(&Value::from(1), &ValueType::Boolean)
+                .unwrap(),
+            Value::from(true)
+        );
+
+        let lossy = registry.with_fallback(Coercion::new().with_policy(CoercionPolicy::Lossy));
+        assert_eq!(
+            lossy
+                .fallback()
+                .convert_numeric(&Value::from(2.5), &NumericType::Integer)
+                .unwrap(),
+            Value::from(2)
+        );
+    }
+
+    #[test]
+    fn named_rules() {
+        let registry = registry();
+        let convert = |text: &str| {
+            registry.convert_with_rule(
+                "fiscalQuarter",
+                &Value::Text(text.into()),
+                &ValueType::DateTime,
+            )
+        };
+        assert_eq!(
+            convert("FY2021Q1").unwrap(),
+            Value::from(DateTime::ymd(2020, 4, 1))
+        );
+        assert_eq!(
+            convert("FY2021Q4").unwrap(),
+            Value::from(DateTime::ymd(2021, 1, 1))
+        );
+        convert("FY2021Q5").unwrap_err();
+
+        assert!(matches!(
+            registry.convert_with_rule("missing", &Value::from(1), &ValueType::Text),
+            Err(CoercionError::UnknownRule(ref name)) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn closures_as_rules() {
+        let registry =
+            CoercionRegistry::new().with_named_rule("percent", |value: &Value, _: &ValueType| {
+                match value {
+                    Value::Text(s) => s
+                        .trim_end_matches('%')
+                        .parse::<f64>()
+                        .map(|p| Value::from(p / 100.0))
+                        .map_err(|_| CoercionError::UnexpectedType),
+                    _ => Err(CoercionError::UnexpectedType),
+                }
+            });
+        assert_eq!(
+            registry
+                .convert_with_rule("percent", &Value::Text("25%".into()), &ValueType::Number)
+                .unwrap(),
+            Value::from(0.25)
+        );
+    }
+}
+
+mod composite_coercion {
+    use datakit::errors::*;
+    use datakit::value::coercion::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use std::str::FromStr;
+
+    use crate::fixtures::*;
+
+    #[test]
+    fn json_text_to_composite() {
+        let coercion = Coercion::new();
+        let value = coercion
+            .convert(
+                &Value::Text(r#" {"a": [1, null], "b": "x"} "#.into()),
+                &ValueType::Composite,
+            )
+            .unwrap();
+        assert_eq!(
+            value,
+            Value::Composite(Collection::Object(vec![
+                (
+                    "a".into(),
+                    array(vec![1.into(), Value::Missing(Empty::Expected)])
+                ),
+                ("b".into(), Value::Text("x".into())),
+            ]))
+        );
+
+        assert!(matches!(
+            coercion.convert(&Value::Text("42".into()), &ValueType::Composite),
+            Err(CoercionError::CoercionFailed { .. })
+        ));
+        coercion
+            .convert(&Value::Text("[1,".into()), &ValueType::Composite)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn composite_to_json_text() {
+        let coercion = Coercion::new();
+        let value = array(vec![
+            Value::from(BigDecimal::from_str("1.50").unwrap()),
+            Value::Number(Numeric::Complex(1.0, 2.0)),
+            Value::from(DateTime::ymd(2021, 3, 4)),
+            Value::Missing(Empty::Expected),
+            Value::from(true),
+        ]);
+        let text = coercion.convert(&value, &ValueType::Text).unwrap();
+        assert_eq!(
+            text,
+            Value::Text(r#"[1.50,"1+2i","2021-03-04",null,true]"#.into())
+        );
+
+        coercion
+            .convert(&array(vec![f64::NAN.into()]), &ValueType::Text)
+            .unwrap_err();
+        coercion
+            .convert(&array(vec![]), &ValueType::Number)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn element_wise() {
+        let coercion = Coercion::new();
+        let value = array(vec![
+            Value::Text("1".into()),
+            Value::Text(" 2 ".into()),
+            Value::Missing(Empty::Expected),
+        ]);
+        let mut losses = Vec::new();
+        let numbers = coercion
+            .convert_elements_reporting(&value, &ValueType::Number, &mut losses)
+            .unwrap();
+        assert_eq!(
+            numbers,
+            array(vec![1.into(), 2.into(), Value::Missing(Empty::Expected)])
+        );
+        assert_eq!(losses.len(), 1);
+
+        assert_eq!(
+            coercion
+                .convert_elements(&Value::Text("[1, true]".into()), &ValueType::Text)
+                .unwrap(),
+            array(vec![Value::Text("1".into()), Value::Text("true".into())])
+        );
+
+        coercion
+            .convert_elements(&array(vec![Value::Text("x".into())]), &ValueType::Number)
+            .unwrap_err();
+        coercion
+            .convert_elements(&Value::from(1), &ValueType::Number)
+            .unwrap_err();
+    }
+}
+
+mod structured_types {
+    use datakit::errors::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::primitives::*;
+    use datakit::value::traits::*;
+    use serde_json::json;
+
+    use crate::fixtures::*;
+
+    fn is(value_type: ValueType) -> TypeConstraint {
+        TypeConstraint::IsType(value_type)
+    }
+
+    fn array_of(item: TypeConstraint) -> TypeConstraint {
+        TypeConstraint::ArrayOf(Box::new(item))
+    }
+
+    fn paths(errors: &[ConstraintError]) -> Vec<&str> {
+        errors
+            .iter()
+            .map(|error| match error {
+                ConstraintError::AtPath { path, .. } => path.as_str(),
+                _ => "",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn arrays_are_checked_item_by_item() {
+        let numbers = array_of(is(ValueType::Number));
+        numbers
+            .validate(&array(vec![1.into(), 2.5.into()]))
+            .unwrap();
+        numbers.validate(&array(vec![])).unwrap();
+
+        let errors = failed_constraints(&numbers, &array(vec![1.into(), "x".into(), true.into()]));
+        assert_eq!(paths(&errors), vec!["/1", "/2"]);
+        assert!(matches!(
+            &errors[0],
+            ConstraintError::AtPath { error, .. } if matches!(
+                **error,
+                ConstraintError::TypeError { expected: ValueType::Number, received: ValueType::Text }
+            )
+        ));
+
+        assert!(matches!(
+            failed_constraints(&numbers, &Value::from(1)).as_slice(),
+            [ConstraintError::TypeError { .. }]
+        ));
+        assert!(matches!(
+            failed_constraints(&numbers, &object(vec![])).as_slice(),
+            [ConstraintError::StructureError { .. }]
+        ));
+    }
+
+    #[test]
+    fn objects_and_maps() {
+        let address = TypeConstraint::Object(vec![
+            ("city".into(), is(ValueType::Text)),
+            ("zip".into(), is(ValueType::Number)),
+        ]);
+        address
+            .validate(&object(vec![
+                ("city", "Paris".into()),
+                ("zip", 75001.into()),
+                ("note", true.into()),
+            ]))
+            .unwrap();
+        let errors = failed_constraints(&address, &object(vec![("city", 1.into())]));
+        assert_eq!(paths(&errors), vec!["/city", "/zip"]);
+
+        let map = TypeConstraint::MapOf(Box::new(array_of(is(ValueType::Number))));
+        let errors = failed_constraints(
+            &map,
+            &object(vec![
+                ("a/b", array(vec![1.into()])),
+                ("c~d", array(vec!["x".into()])),
+            ]),
+        );
+        assert_eq!(paths(&errors), vec!["/c~0d/0"]);
+    }
+
+    #[test]
+    fn contracts_report_nested_errors() {
+        let contract = ValueContract::new(array_of(is(ValueType::Text)), vec![]);
+        contract.validate(&array(vec!["a".into()])).unwrap();
+        contract.validate(&array(vec![1.into()])).unwrap_err();
+    }
+
+    #[test]
+    fn inference() {
+        let value = object(vec![
+            ("tags", array(vec!["a".into(), "b".into()])),
+            (
+                "scores",
+                array(vec![1.into(), Value::Missing(Empty::Expected)]),
+            ),
+            ("mixed", array(vec![1.into(), "a".into()])),
+            ("empty", array(vec![])),
+        ]);
+        let inferred = TypeConstraint::infer(&value);
+        assert_eq!(
+            inferred,
+            TypeConstraint::Object(vec![
+                ("tags".into(), array_of(is(ValueType::Text))),
+                ("scores".into(), array_of(is(ValueType::Number))),
+                ("mixed".into(), is(ValueType::Composite)),
+                ("empty".into(), is(ValueType::Composite)),
+            ])
+        );
+
+        let rows = vec![
+            object(vec![("a", 1.into()), ("b", array(vec![]))]),
+            Value::Missing(Empty::Expected),
+            object(vec![("b", array(vec![true.into()])), ("c", "x".into())]),
+        ];
+        assert_eq!(
+            TypeConstraint::infer_all(&rows),
+            Some(TypeConstraint::Object(vec![(
+                "b".into(),
+                array_of(is(ValueType::Boolean))
+            )]))
+        );
+        assert_eq!(TypeConstraint::infer_all(&[1.into(), "a".into()]), None);
+        assert_eq!(TypeConstraint::infer_all(&[]), None);
+    }
+
+    #[test]
+    fn json_schema_mapping() {
+        let constraint = TypeConstraint::Object(vec![
+            ("id".into(), is(ValueType::Number)),
+            ("tags".into(), array_of(is(ValueType::Text))),
+            (
+                "limits".into(),
+                TypeConstraint::MapOf(Box::new(is(ValueType::Duration))),
+            ),
+        ]);
+        let schema = constraint.to_json_schema();
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "id": {"type": "number"},
+                    "tags": {"type": "array", "items": {"type": "string"}},
+                    "limits": {
+                        "type": "object",
+                        "additionalProperties": {"type": "string", "format": "duration"}
+                    }
+                },
+                "required": ["id", "tags", "limits"]
+            })
+        );
+
+        let read = TypeConstraint::from_json_schema(&schema).unwrap();
+        match read {
+            TypeConstraint::Object(mut fields) => {
+                fields.sort_by(|a, b| a.0.cmp(&b.0));
+                assert_eq!(
+                    fields,
+                    vec![
+                        ("id".into(), is(ValueType::Number)),
+                        (
+                            "limits".into(),
+                            TypeConstraint::MapOf(Box::new(is(ValueType::Duration)))
+                        ),
+                        ("tags".into(), array_of(is(ValueType::Text))),
+                    ]
+                );
+            }
+            other => panic!("Unexpected constraint {:?}", other),
+        }
+
+        assert_eq!(
+            TypeConstraint::from_json_schema(&json!({"type": "string", "format": "date"})),
+            Some(is(ValueType::DateTime))
+        );
+        assert_eq!(
+            TypeConstraint::from_json_schema(&json!({"type": "integer"})),
+            Some(is(ValueType::Number))
+        );
+        assert_eq!(
+            TypeConstraint::from_json_schema(&json!({"type": "foo"})),
+            None
+        );
+        assert_eq!(TypeConstraint::from_json_schema(&json!({})), None);
+    }
+}
+
+mod composite_constraints {
+    use datakit::errors::*;
+    use datakit::value::constraints::*;
+    use datakit::value::definitions::*;
+    use datakit::value::traits::*;
+
+    use crate::fixtures::*;
+
+    fn contract(value_type: ValueType, constraints: Vec<ValueConstraint>) -> ValueContract {
+        ValueContract::new(TypeConstraint::IsType(value_type), constraints)
+    }
+
+    /// The path of each error, and the error without its path.
+    fn with_paths(errors: Vec<ConstraintError>) -> Vec<(String, ConstraintError)> {
+        errors
+            .into_iter()
+            .map(|error| match error {
+                ConstraintError::AtPath { path, error } => (path, *error),
+                error => (String::new(), error),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn item_counts() {
+        let value = array(vec![1.into(), 2.into(), 3.into()]);
+        ValueConstraint::MaximumLength(3).validate(&value).unwrap();
+        ValueConstraint::MaximumLength(2)
+            .validate(&value)
+            .unwrap_err();
+        ValueConstraint::MinimumLength(1)
+            .validate(&object(vec![("a", 1.into())]))
+            .unwrap();
+        ValueConstraint::MinimumLength(2)
+            .validate(&object(vec![("a", 1.into())]))
+            .unwrap_err();
+        assert!(matches!(
+            failed_constraints(&ValueConstraint::MaximumLength(2), &Value::from(1)).as_slice(),
+            [ConstraintError::InvalidConstraintError]
+        ));
+    }
+
+    #[test]
+    fn unique_items() {
+        ValueConstraint::UniqueItems
+            .validate(&array(vec![1.into(), 2.into()]))
+            .unwrap();
+        let located = with_paths(failed_constraints(
+            &ValueConstraint::UniqueItems,
+            &array(vec![1.into(), 2.into(), 1.into(), 1.into()]),
+        ));
+        let paths: Vec<_> = located.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, vec!["/2", "/3"]);
+    }
+
+    #[test]
+    fn every_item() {
+        let positive = ValueConstraint::EveryItem(Box::new(contract(
+            ValueType::Number,
+            vec![ValueConstraint::Minimum(0.into())],
+        )));
+        positive.validate(&array(vec![1.into(), 2.into()])).unwrap();
+        let located = with_paths(failed_constraints(
+            &positive,
+            &array(vec![1.into(), (-1).into(), "x".into()]),
+        ));
+        assert_eq!(located.len(), 2);
+        assert_eq!(located[0].0, "/1");
+        assert!(matches!(
+            located[0].1,
+            ConstraintError::InvalidValueError(ValueConstraint::Minimum(_))
+        ));
+        assert_eq!(located[1].0, "/2");
+        assert!(matches!(located[1].1, ConstraintError::TypeError { .. }));
+    }
+
+    #[test]
+    fn required_keys() {
+        let required = ValueConstraint::RequiredKeys(vec!["id".into(), "name".into()]);
+        required
+            .validate(&object(vec![("id", 1.into()), ("name", "a".into())]))
+            .unwrap();
+        assert!(matches!(
+            failed_constraints(&required, &object(vec![("id", 1.into())])).as_slice(),
+            [ConstraintError::MissingKey(key)] if key == "name"
+        ));
+        assert!(matches!(
+            failed_constraints(&required, &array(vec![])).as_slice(),
+            [ConstraintError::InvalidConstraintError]
+        ));
+    }
+
+    fn address(additional: AdditionalProperties) -> ValueConstraint {
+        ValueConstraint::Properties {
+            properties: vec![
+                (
+                    "city".into(),
+                    contract(ValueType::Text, vec![ValueConstraint::MinimumLength(1)]),
+                ),
+                ("zip".into(), contract(ValueType::Number, vec![])),
+            ],
+            additional,
+        }
+    }
+
+    #[test]
+    fn properties_and_additional_properties() {
+        let value = object(vec![
+            ("city", "".into()),
+            ("zip", 75001.into()),
+            ("note", "x".into()),
+        ]);
+
+        let located = with_paths(failed_constraints(
+            &address(AdditionalProperties::Allow),
+            &value,
+        ));
+        assert_eq!(located.len(), 1);
+        assert_eq!(located[0].0, "/city");
+
+        let forbidden = failed_constraints(&address(AdditionalProperties::Forbid), &value);
+        assert!(matches!(
+            &forbidden[1],
+            ConstraintError::UnexpectedKey(key) if key == "note"
+        ));
+
+        let numbers = AdditionalProperties::Satisfy(Box::new(contract(ValueType::Number, vec![])));
+        let located = with_paths(failed_constraints(&address(numbers), &value));
+        let paths: Vec<_> = located.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, vec!["/city", "/note"]);
+
+        address(AdditionalProperties::Allow)
+            .validate(&object(vec![("zip", 1.into())]))
+            .unwrap();
+    }
+
+    #[test]
+    fn nested_paths_are_joined() {
+        let customers = ValueConstraint::EveryItem(Box::new(ValueContract::new(
+            TypeConstraint::IsType(ValueType::Composite),
+            vec![address(AdditionalProperties::Forbid)],
+        )));
+        let value = array(vec![
+            object(vec![("city", "Paris".into())]),
+            object(vec![("city", "".into()), ("zip", "x".into())]),
+        ]);
+        let located = with_paths(failed_constraints(&customers, &value));
+        let paths: Vec<_> = located.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, vec!["/1/city", "/1/zip"]);
+
+        let message = ConstraintError::AtPath {
+            path: "/1/zip".into(),
+            error: Box::new(ConstraintError::TypeError {
+                expected: ValueType::Number,
+                received: ValueType::Text,
+            }),
+        }
+        .to_string();
+        assert_eq!(message, "Encountered unexpected value type at /1/zip");
+    }
+}
+
+mod value_paths {
+    use datakit::errors::*;
+    use datakit::value::definitions::*;
+    use datakit::value::path::*;
+
+    use crate::fixtures::*;
+
+    fn person() -> Value {
+        object(vec![
+            ("name", "Ada".into()),
+            (
+                "address",
+                object(vec![
+                    ("city", "London".into()),
+                    ("lines", array(vec!["12 St James's Square".into()])),
+                ]),
+            ),
+            ("a/b~c", 1.into()),
+        ])
+    }
+
+    #[test]
+    fn parse_paths() {
+        let pointer: ValuePath = "/address/lines/0".parse().unwrap();
+        assert_eq!(pointer.segments(), ["address", "lines", "0"]);
+        assert_eq!("address.lines.0".parse::<ValuePath>().unwrap(), pointer);
+        assert_eq!(pointer.to_string(), "/address/lines/0");
+
+        let escaped: ValuePath = "/a~1b~0c".parse().unwrap();
+        assert_eq!(escaped.segments(), ["a/b~c"]);
+        assert_eq!(escaped.to_string(), "/a~1b~0c");
+
+        assert_eq!("".parse::<ValuePath>().unwrap(), ValuePath::root());
+        assert_eq!(
+            "/".parse::<ValuePath>().unwrap().segments(),
+            [String::new()]
+        );
+        for invalid in &["/a~2", "/a~", "a..b", ".a", "a."] {
+            assert_eq!(
+                invalid.parse::<ValuePath>(),
+                Err(PathError::InvalidPath(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn get() {
+        let value = person();
+        assert_eq!(value.get("address.city"), Some(&"London".into()));
+        assert_eq!(value.get("/address/city"), Some(&"London".into()));
+        assert_eq!(
+            value.get("/address/lines/0"),
+            Some(&"12 St James's Square".into())
+        );
+        assert_eq!(value.get("/a~1b~0c"), Some(&1.into()));
+        assert_eq!(value.get(""), Some(&value));
+
+        assert_eq!(value.get("address.country"), None);
+        assert_eq!(value.get("address.lines.1"), None);
+        assert_eq!(value.get("address.lines.00"), None);
+        assert_eq!(value.get("address.lines.-"), None);
+        assert_eq!(value.get("name.first"), None);
+        assert_eq!(value.get("a..b"), None);
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut value = person();
+        *value.get_mut("address.city").unwrap() = "Paris".into();
+        assert_eq!(value.get("address.city"), Some(&"Paris".into()));
+        assert!(value.get_mut("address.country").is_none());
+    }
+
+    #[test]
+    fn set() {
+        let mut value = person();
+        assert_eq!(
+            value.set("address.city", "Paris".into()),
+            Ok(Some("London".into()))
+        );
+        assert_eq!(value.set("address.country", "France".into()), Ok(None));
+        assert_eq!(value.get("address.country"), Some(&"France".into()));
+
+        assert_eq!(value.set("/address/lines/-", "Paris".into()), Ok(None));
+        assert_eq!(value.set("/address/lines/2", "France".into()), Ok(None));
+        assert_eq!(
+            value.set("/address/lines/0", "1 Rue de Rivoli".into()),
+            Ok(Some("12 St James's Square".into()))
+        );
+        assert_eq!(
+            value.get("address.lines"),
+            Some(&array(vec![
+                "1 Rue de Rivoli".into(),
+                "Paris".into(),
+                "France".into()
+            ]))
+        );
+
+        assert_eq!(
+            value.set("/address/lines/4", "".into()),
+            Err(PathError::NotFound("/address/lines/4".into()))
+        );
+        assert_eq!(
+            value.set("work.city", "Paris".into()),
+            Err(PathError::NotFound("/work/city".into()))
+        );
+        assert_eq!(
+            value.set("name.first", "Ada".into()),
+            Err(PathError::NotFound("/name/first".into()))
+        );
+        assert_eq!(
+            value.set("a..b", 1.into()),
+            Err(PathError::InvalidPath("a..b".into()))
+        );
+
+        let mut number = Value::from(1);
+        assert_eq!(number.set("", 2.into()), Ok(Some(1.into())));
+        assert_eq!(number, Value::from(2));
+    }
+}